@@ -0,0 +1,652 @@
+// Provides reading of continuous-energy neutron ACE tables, as produced by NJOY
+//
+// A file holds one or more tables of the same nuclide, usually at different
+// temperatures. Each table has a header (name, atomic weight ratio, kT and the
+// NXS and JXS arrays) and a single XSS array with all data, located by JXS:
+//
+//   ESZ  (JXS 1)  energies (MeV), total, absorption, elastic and heating, NES values each
+//   MTR  (JXS 3)  MT numbers of the NTR reactions other than elastic
+//   LSIG (JXS 6)  locator of each reaction's cross section relative to SIG
+//   SIG  (JXS 7)  for each reaction: first energy index IE, count NE, NE values
+//
+// Type 1 tables are ASCII with the legacy or the 2.0 header. Type 2 tables are
+// binary with the layout written by NJOY and read by OpenMC: fixed records of
+// 4096 bytes, the header in the first record and the XSS array from the second.
+use crate::doppler::BOLTZMANN;
+use crate::nuclide::{Nuclide, TemperatureSelector, FISSION_MTS};
+use crate::nuclide_id::NuclideId;
+use crate::reaction::Reaction;
+use crate::temperature::parse_temperature;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Record length of binary (type 2) ACE files in bytes.
+const BINARY_RECORD_LENGTH: usize = 4096;
+
+/// Size of the binary header: name, AWR, kT, date, comment, material, 16 IZ/AW
+/// pairs, NXS and JXS.
+const BINARY_HEADER_LENGTH: usize = 10 + 8 + 8 + 10 + 70 + 10 + 16 * 12 + 16 * 4 + 32 * 4;
+
+/// An ACE table: the header values that are used and the XSS array.
+struct AceTable {
+    name: String,
+    awr: f64,
+    temperature: String,
+    nxs: [usize; 16],
+    jxs: [usize; 32],
+    xss: Vec<f64>,
+}
+
+/// Header of a table, read before deciding whether to read its XSS array.
+struct AceHeader {
+    name: String,
+    awr: f64,
+    /// kT in MeV
+    kt: f64,
+    nxs: [usize; 16],
+    jxs: [usize; 32],
+}
+
+/// Tables read from a file.
+struct AceTables {
+    /// Temperatures of every table
+    available: Vec<String>,
+    /// Name and AWR of the first table, kept whether or not it is selected
+    first: Option<(String, f64)>,
+    /// Tables at the selected temperatures
+    tables: Vec<AceTable>,
+}
+
+impl AceHeader {
+    /// Temperature key of the table, kT converted to K and rounded as in the
+    /// JSON data (293.6 K gives "294").
+    fn temperature(&self) -> String {
+        format!("{}", (self.kt * 1.0e6 / BOLTZMANN).round() as i64)
+    }
+
+    fn into_table(self, xss: Vec<f64>) -> AceTable {
+        AceTable {
+            temperature: self.temperature(),
+            name: self.name,
+            awr: self.awr,
+            nxs: self.nxs,
+            jxs: self.jxs,
+            xss,
+        }
+    }
+}
+
+/// True when `bytes`, the start of a file, look like an ACE table: a name such
+/// as `"92235.80c"` (ASCII or binary) or a 2.0 header.
+pub(crate) fn looks_like_ace(bytes: &[u8]) -> bool {
+    let start = &bytes[..bytes.len().min(24)];
+    let text = String::from_utf8_lossy(start);
+    let name = text.split_whitespace().next().unwrap_or("");
+    name.starts_with("2.0.")
+        || name.split_once('.').is_some_and(|(za, suffix)| {
+            !za.is_empty() && za.chars().all(|c| c.is_ascii_digit()) && suffix.ends_with('c')
+        })
+}
+
+/// True when the file at `path` has an `.ace` extension or starts like an ACE table.
+pub(crate) fn is_ace_file(path: &Path) -> bool {
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ace"))
+    {
+        return true;
+    }
+    let mut start = [0u8; 24];
+    use std::io::Read;
+    let read = std::fs::File::open(path).and_then(|mut file| file.read(&mut start));
+    read.is_ok_and(|n| looks_like_ace(&start[..n]))
+}
+
+/// Read the continuous-energy neutron ACE tables of a file into a nuclide,
+/// keeping only the given temperatures (`None` or an empty set keeps all but
+/// 0 K tables, see [`crate::temperature::default_temperatures`]).
+///
+/// ASCII (type 1) and binary (type 2) files are recognized automatically.
+/// Each table of the file becomes one temperature, with its energy grid
+/// converted to eV, the total (MT 1), elastic (MT 2) and every reaction of the
+/// MTR block with its threshold. Summed reactions missing from the table are
+/// reconstructed (see [`Nuclide::reconstruct_redundant_reactions`]).
+/// `available_temperatures` lists the temperatures of every table.
+pub fn read_nuclide_from_ace<P: AsRef<Path>>(
+    path: P,
+    temperatures: Option<&HashSet<String>>,
+) -> Result<Nuclide, Box<dyn std::error::Error>> {
+    let path = path.as_ref();
+    let bytes = std::fs::read(path)?;
+    let mut nuclide = read_nuclide_from_ace_bytes(&bytes, temperatures)
+        .map_err(|e| format!("{}: {}", path.to_string_lossy(), e))?;
+    nuclide.data_path = Some(path.to_string_lossy().to_string());
+    Ok(nuclide)
+}
+
+/// Read an ACE file like [`read_nuclide_from_ace`], keeping the temperatures
+/// chosen by `select` from those of all tables in the file.
+pub(crate) fn read_nuclide_from_ace_selecting(
+    path: &Path,
+    select: &TemperatureSelector,
+) -> Result<Nuclide, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    let label = |e: String| format!("{}: {}", path.to_string_lossy(), e);
+    // Only the headers are parsed to list the temperatures
+    let available = read_tables(&bytes, |_| false).map_err(label)?.available;
+    let temperatures = select(&available);
+    let mut nuclide = read_nuclide_from_ace_bytes(&bytes, Some(&temperatures)).map_err(label)?;
+    nuclide.data_path = Some(path.to_string_lossy().to_string());
+    Ok(nuclide)
+}
+
+/// Read the ACE tables held in memory into a nuclide, as [`read_nuclide_from_ace`] does.
+pub fn read_nuclide_from_ace_bytes(
+    bytes: &[u8],
+    temperatures: Option<&HashSet<String>>,
+) -> Result<Nuclide, String> {
+    let requested = temperatures.filter(|s| !s.is_empty());
+    let selected = |t: &str| requested.map_or(parse_temperature(t) != Some(0.0), |s| s.contains(t));
+    let mut read = read_tables(bytes, selected)?;
+    // 0 K tables are only loaded by default when there are no others
+    if requested.is_none() && read.tables.is_empty() {
+        read = read_tables(bytes, |_| true)?;
+    }
+    nuclide_from_tables(read)
+}
+
+/// Read every table of an ASCII or binary file, keeping the XSS arrays of
+/// the tables at the selected temperatures.
+fn read_tables(bytes: &[u8], selected: impl Fn(&str) -> bool) -> Result<AceTables, String> {
+    // Binary headers hold the AWR and kT as raw floats right after the name
+    let ascii = bytes
+        .iter()
+        .take(128)
+        .all(|&b| b.is_ascii_graphic() || b.is_ascii_whitespace());
+    if ascii {
+        let text = std::str::from_utf8(bytes).map_err(|e| format!("Invalid ACE file: {}", e))?;
+        read_ascii_tables(text, selected)
+    } else {
+        read_binary_tables(bytes, selected)
+    }
+}
+
+/// Parse the whitespace separated numbers of `line`.
+fn parse_numbers<T: std::str::FromStr>(line: &str, what: &str) -> Result<Vec<T>, String> {
+    line.split_whitespace()
+        .map(|word| {
+            word.parse::<T>()
+                .map_err(|_| format!("Invalid {} value '{}' in ACE header", what, word))
+        })
+        .collect()
+}
+
+/// Convert the NXS or JXS values of a header to array indices.
+fn header_array<const N: usize>(values: &[i64], what: &str) -> Result<[usize; N], String> {
+    if values.len() != N {
+        return Err(format!("Expected {} {} values in ACE header, found {}", N, what, values.len()));
+    }
+    let mut array = [0; N];
+    for (slot, &value) in array.iter_mut().zip(values) {
+        *slot = usize::try_from(value)
+            .map_err(|_| format!("Negative {} value {} in ACE header", what, value))?;
+    }
+    Ok(array)
+}
+
+/// Read every table of an ASCII (type 1) file, keeping the XSS arrays of the
+/// tables at the selected temperatures.
+fn read_ascii_tables(text: &str, selected: impl Fn(&str) -> bool) -> Result<AceTables, String> {
+    let mut lines = text.lines().peekable();
+    let mut read = AceTables {
+        available: Vec::new(),
+        first: None,
+        tables: Vec::new(),
+    };
+    loop {
+        while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+            lines.next();
+        }
+        let Some(first) = lines.next() else { break };
+        let mut next_line = || lines.next().ok_or("ACE file ends inside a table header".to_string());
+
+        let words: Vec<&str> = first.split_whitespace().collect();
+        let (name, awr, kt) = if words[0].starts_with("2.0.") {
+            // 2.0 header: version, name and source, then AWR, kT, date and the
+            // number of comment lines that follow
+            let name = words.get(1).ok_or("ACE 2.0 header without a table name")?.to_string();
+            let second = next_line()?;
+            let values: Vec<&str> = second.split_whitespace().collect();
+            if values.len() < 4 {
+                return Err(format!("Invalid ACE 2.0 header line '{}'", second.trim()));
+            }
+            let comments: usize = values[3]
+                .parse()
+                .map_err(|_| format!("Invalid number of comment lines '{}' in ACE 2.0 header", values[3]))?;
+            for _ in 0..comments {
+                next_line()?;
+            }
+            (name, values[0], values[1])
+        } else {
+            if words.len() < 3 {
+                return Err(format!("Invalid ACE header line '{}'", first.trim()));
+            }
+            next_line()?; // Comment and material
+            (words[0].to_string(), words[1], words[2])
+        };
+        let awr: f64 = awr.parse().map_err(|_| format!("Invalid atomic weight ratio '{}' in ACE header", awr))?;
+        let kt: f64 = kt.parse().map_err(|_| format!("Invalid temperature '{}' in ACE header", kt))?;
+        for _ in 0..4 {
+            next_line()?; // IZ/AW pairs
+        }
+        let nxs: Vec<i64> = parse_numbers(&format!("{} {}", next_line()?, next_line()?), "NXS")?;
+        let mut jxs_text = String::new();
+        for _ in 0..4 {
+            jxs_text.push_str(next_line()?);
+            jxs_text.push(' ');
+        }
+        let jxs: Vec<i64> = parse_numbers(&jxs_text, "JXS")?;
+        let header = AceHeader {
+            name,
+            awr,
+            kt,
+            nxs: header_array(&nxs, "NXS")?,
+            jxs: header_array(&jxs, "JXS")?,
+        };
+
+        // XSS values, read only for the selected temperatures
+        let length = header.nxs[0];
+        let temperature = header.temperature();
+        let keep = selected(&temperature);
+        let mut xss = Vec::with_capacity(if keep { length } else { 0 });
+        let mut count = 0;
+        while count < length {
+            let line = lines.next().ok_or_else(|| {
+                format!(
+                    "ACE table {} ends after {} of its {} XSS values",
+                    header.name, count, length
+                )
+            })?;
+            for word in line.split_whitespace() {
+                count += 1;
+                if keep {
+                    xss.push(word.parse::<f64>().map_err(|_| {
+                        format!("Invalid XSS value '{}' in ACE table {}", word, header.name)
+                    })?);
+                }
+            }
+        }
+        if count != length {
+            return Err(format!(
+                "ACE table {} has {} XSS values but NXS(1) is {}",
+                header.name, count, length
+            ));
+        }
+        read.first.get_or_insert_with(|| (header.name.clone(), header.awr));
+        read.available.push(temperature);
+        if keep {
+            read.tables.push(header.into_table(xss));
+        }
+    }
+    Ok(read)
+}
+
+/// Read every table of a binary (type 2) file, keeping the XSS arrays of the
+/// tables at the selected temperatures.
+fn read_binary_tables(bytes: &[u8], selected: impl Fn(&str) -> bool) -> Result<AceTables, String> {
+    let f64_at = |pos: usize| f64::from_le_bytes(bytes[pos..pos + 8].try_into().unwrap());
+    let i32_at = |pos: usize| i32::from_le_bytes(bytes[pos..pos + 4].try_into().unwrap()) as i64;
+    let mut read = AceTables {
+        available: Vec::new(),
+        first: None,
+        tables: Vec::new(),
+    };
+    let mut start = 0;
+    while start < bytes.len() {
+        if bytes.len() - start < BINARY_HEADER_LENGTH {
+            return Err("Binary ACE file ends inside a table header".to_string());
+        }
+        let name = String::from_utf8_lossy(&bytes[start..start + 10]).trim().to_string();
+        let awr = f64_at(start + 10);
+        let kt = f64_at(start + 18);
+        // Date, comment, material and IZ/AW pairs are not used
+        let nxs_start = start + 10 + 8 + 8 + 10 + 70 + 10 + 16 * 12;
+        let nxs: Vec<i64> = (0..16).map(|i| i32_at(nxs_start + 4 * i)).collect();
+        let jxs: Vec<i64> = (0..32).map(|i| i32_at(nxs_start + 64 + 4 * i)).collect();
+        let header = AceHeader {
+            name,
+            awr,
+            kt,
+            nxs: header_array(&nxs, "NXS")?,
+            jxs: header_array(&jxs, "JXS")?,
+        };
+
+        let length = header.nxs[0];
+        let xss_start = start + BINARY_RECORD_LENGTH;
+        if xss_start + 8 * length > bytes.len() {
+            return Err(format!(
+                "Binary ACE table {} is truncated, NXS(1) is {} values",
+                header.name, length
+            ));
+        }
+        let temperature = header.temperature();
+        read.first.get_or_insert_with(|| (header.name.clone(), header.awr));
+        if selected(&temperature) {
+            let xss = (0..length).map(|i| f64_at(xss_start + 8 * i)).collect();
+            read.tables.push(header.into_table(xss));
+        }
+        read.available.push(temperature);
+        // The next table starts at the record after the XSS array
+        start = xss_start + length.div_ceil(BINARY_RECORD_LENGTH / 8) * BINARY_RECORD_LENGTH;
+    }
+    Ok(read)
+}
+
+/// Nuclide identity from an ACE table name: a ZAID such as `"3006.80c"`
+/// (MCNP metastable offsets allowed, including 95242 for Am242_m1) or a 2.0 style SZA such as `"1095242.00c"`.
+fn table_nuclide_id(name: &str) -> Result<NuclideId, String> {
+    let za = name.split('.').next().unwrap_or(name);
+    match za.parse::<u32>() {
+        Ok(sza) if sza >= 1_000_000 => {
+            let za = sza % 1_000_000;
+            NuclideId::new(za / 1000, za % 1000, sza / 1_000_000)
+        }
+        _ => crate::nuclide_names::parse_nuclide_name(za),
+    }
+    .map_err(|e| format!("Cannot identify the nuclide of ACE table {}: {}", name, e))
+}
+
+/// Build a nuclide from continuous-energy neutron tables of a single nuclide.
+fn nuclide_from_tables(read: AceTables) -> Result<Nuclide, String> {
+    let AceTables { available, first, tables } = read;
+    let (first_name, first_awr) = first.ok_or("ACE file contains no tables")?;
+    let table_id = |name: &str| {
+        if !name.ends_with('c') {
+            return Err(format!(
+                "ACE table {} is not a continuous-energy neutron table, only those (names ending in 'c') are supported",
+                name
+            ));
+        }
+        table_nuclide_id(name)
+    };
+    let mut energy_map = HashMap::new();
+    let mut reactions = HashMap::new();
+    let mut identity: Option<(String, NuclideId, f64)> = None;
+    for table in &tables {
+        let id = table_id(&table.name)?;
+        match &identity {
+            Some((name, first_id, _)) if *first_id != id => {
+                return Err(format!(
+                    "ACE file mixes tables of different nuclides: {} and {}",
+                    name, table.name
+                ))
+            }
+            Some(_) => {}
+            None => identity = Some((table.name.clone(), id, table.awr)),
+        }
+        if energy_map.contains_key(&table.temperature) {
+            return Err(format!(
+                "ACE file has several tables at {} K, including {}",
+                table.temperature, table.name
+            ));
+        }
+        let (grid, temp_reactions) = read_table_reactions(table)?;
+        energy_map.insert(table.temperature.clone(), grid);
+        reactions.insert(table.temperature.clone(), temp_reactions);
+    }
+
+    let mut available_temperatures = available;
+    available_temperatures.sort();
+    available_temperatures.dedup();
+    let mut loaded_temperatures: Vec<String> = reactions.keys().cloned().collect();
+    loaded_temperatures.sort();
+
+    // Identity comes from the first table when none was loaded
+    let (id, awr) = match identity {
+        Some((_, id, awr)) => (id, awr),
+        None => (table_id(&first_name)?, first_awr),
+    };
+    let mut nuclide = Nuclide {
+        name: Some(id.name()),
+        element: None,
+        atomic_symbol: Some(id.symbol().to_string()),
+        atomic_number: Some(id.atomic_number),
+        neutron_number: id.neutron_number(),
+        mass_number: Some(id.mass_number),
+        metastable: Some(id.metastable),
+        atomic_weight_ratio: Some(awr),
+        library: None,
+        energy: if energy_map.is_empty() { None } else { Some(energy_map) },
+        reactions,
+        fissionable: false,
+        available_temperatures,
+        temperatures_without_data: Vec::new(),
+        loaded_temperatures,
+        data_path: None,
+    };
+    nuclide.reconstruct_redundant_reactions();
+    nuclide.fissionable = nuclide
+        .reactions
+        .values()
+        .any(|temp_reactions| temp_reactions.keys().any(|mt| FISSION_MTS.contains(mt)));
+    Ok(nuclide)
+}
+
+/// Energy grid in eV and reactions of a table.
+fn read_table_reactions(table: &AceTable) -> Result<(Vec<f64>, HashMap<i32, Reaction>), String> {
+    let xss = &table.xss;
+    let nes = table.nxs[2];
+    let ntr = table.nxs[3];
+    let [esz, _, mtr, _, _, lsig, sig] = [0, 1, 2, 3, 4, 5, 6].map(|i| table.jxs[i]);
+    // Slice of XSS from a 1-based JXS locator
+    let block = |start: usize, len: usize, what: &str| {
+        start
+            .checked_sub(1)
+            .and_then(|begin| xss.get(begin..begin + len))
+            .ok_or_else(|| {
+                format!(
+                    "ACE table {}: {} block at {} with {} values is outside the XSS array of {} values",
+                    table.name, what, start, len, xss.len()
+                )
+            })
+    };
+
+    let grid: Vec<f64> = block(esz, nes, "energy")?.iter().map(|e| e * 1.0e6).collect();
+    let mut reactions = HashMap::new();
+    let mut add = |mt: i32, threshold_idx: usize, cross_section: Vec<f64>| {
+        let energy = grid[threshold_idx..].to_vec();
+        reactions.insert(
+            mt,
+            Reaction {
+                cross_section,
+                threshold_idx,
+                interpolation: vec![2],
+                breakpoints: Vec::new(),
+                energy,
+                mt_number: mt,
+                derived: false,
+            },
+        );
+    };
+    add(1, 0, block(esz + nes, nes, "total")?.to_vec());
+    add(2, 0, block(esz + 3 * nes, nes, "elastic")?.to_vec());
+
+    let mts = block(mtr, ntr, "MTR")?;
+    let locators = block(lsig, ntr, "LSIG")?;
+    for (&mt, &locator) in mts.iter().zip(locators) {
+        let mt = mt as i32;
+        let start = (sig + locator as usize).saturating_sub(1);
+        let head = block(start, 2, "reaction")?;
+        let (ie, ne) = (head[0] as usize, head[1] as usize);
+        if ie == 0 || ie - 1 + ne != nes {
+            return Err(format!(
+                "ACE table {}: MT {} starts at energy {} with {} values but the grid has {} energies",
+                table.name, mt, ie, ne, nes
+            ));
+        }
+        add(mt, ie - 1, block(start + 2, ne, "reaction")?.to_vec());
+    }
+    Ok((grid, reactions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn li6_json() -> Nuclide {
+        crate::nuclide::read_nuclide_from_json("tests/Li6.json", None).unwrap()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() <= 1e-10 * x.abs().max(y.abs()), "{} != {}", x, y);
+        }
+    }
+
+    /// A binary (type 2) file holding the tables of an ASCII file.
+    fn to_binary(text: &str) -> Vec<u8> {
+        let tables = read_ascii_tables(text, |_| true).unwrap().tables;
+        let mut bytes = Vec::new();
+        for table in tables {
+            let mut record = format!("{:>10}", table.name).into_bytes();
+            record.extend_from_slice(&table.awr.to_le_bytes());
+            let kt = table.temperature.parse::<f64>().unwrap() * BOLTZMANN * 1.0e-6;
+            record.extend_from_slice(&kt.to_le_bytes());
+            record.extend_from_slice(format!("{:10}{:70}{:10}", "", "", "").as_bytes());
+            record.resize(record.len() + 16 * 12, 0);
+            for value in table.nxs.iter().chain(&table.jxs) {
+                record.extend_from_slice(&(*value as i32).to_le_bytes());
+            }
+            record.resize(BINARY_RECORD_LENGTH, 0);
+            bytes.extend(record);
+            for value in &table.xss {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes.resize(bytes.len().div_ceil(BINARY_RECORD_LENGTH) * BINARY_RECORD_LENGTH, 0);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_ascii_table_matches_json() {
+        let ace = read_nuclide_from_ace("tests/Li6.ace", None).unwrap();
+        let json = li6_json();
+        assert_eq!(ace.name.as_deref(), Some("Li6"));
+        assert_eq!((ace.atomic_number, ace.mass_number, ace.metastable), (Some(3), Some(6), Some(0)));
+        assert_eq!(ace.atomic_weight_ratio, json.atomic_weight_ratio);
+        assert_eq!(ace.available_temperatures, vec!["294"]);
+        assert_eq!(ace.loaded_temperatures, vec!["294"]);
+        assert!(!ace.fissionable);
+        assert_close(&ace.energy.as_ref().unwrap()["294"], &json.energy.as_ref().unwrap()["294"]);
+
+        let (ace_reactions, json_reactions) = (&ace.reactions["294"], &json.reactions["294"]);
+        for (mt, reaction) in ace_reactions {
+            let expected = &json_reactions[mt];
+            assert_eq!(reaction.threshold_idx, expected.threshold_idx, "MT {}", mt);
+            assert_close(&reaction.cross_section, &expected.cross_section);
+            assert_eq!(reaction.energy.len(), reaction.cross_section.len());
+        }
+        // Redundant reactions absent from the table are summed from their components
+        for mt in [3, 4, 27, 101] {
+            assert!(ace_reactions[&mt].derived, "MT {}", mt);
+        }
+        assert!(!ace_reactions[&1].derived);
+        assert!(ace.validate().is_valid(), "{}", ace.validate());
+    }
+
+    #[test]
+    fn test_binary_and_2_0_headers() {
+        let text = std::fs::read_to_string("tests/Li6.ace").unwrap();
+        let ascii = read_nuclide_from_ace_bytes(text.as_bytes(), None).unwrap();
+
+        let binary = read_nuclide_from_ace_bytes(&to_binary(&text), None).unwrap();
+        assert_eq!(binary.reactions, ascii.reactions);
+        assert_eq!(binary.energy, ascii.energy);
+        assert_eq!(binary.atomic_weight_ratio, ascii.atomic_weight_ratio);
+
+        // 2.0 header: version line, AWR/kT line and comment lines in place of
+        // the legacy first two lines
+        let body: Vec<&str> = text.lines().skip(2).collect();
+        let new_style = format!(
+            "2.0.0     3006.00c      ENDF/B-VIII.0\n5.963400 2.5301E-08 2025-01-01 2\ncomment one\ncomment two\n{}\n",
+            body.join("\n")
+        );
+        let nuclide = read_nuclide_from_ace_bytes(new_style.as_bytes(), None).unwrap();
+        assert_eq!(nuclide.name.as_deref(), Some("Li6"));
+        assert_eq!(nuclide.reactions, ascii.reactions);
+    }
+
+    #[test]
+    fn test_several_temperatures_and_filtering() {
+        let text = std::fs::read_to_string("tests/Li6.ace").unwrap();
+        let hot = text.replacen("2.5301E-08", "5.1704E-08", 1);
+        let both = format!("{}{}", text, hot);
+        let nuclide = read_nuclide_from_ace_bytes(both.as_bytes(), None).unwrap();
+        assert_eq!(nuclide.loaded_temperatures, vec!["294", "600"]);
+
+        let only_hot: HashSet<String> = ["600".to_string()].into_iter().collect();
+        let nuclide = read_nuclide_from_ace_bytes(both.as_bytes(), Some(&only_hot)).unwrap();
+        assert_eq!(nuclide.available_temperatures, vec!["294", "600"]);
+        assert_eq!(nuclide.loaded_temperatures, vec!["600"]);
+        let nuclide = read_nuclide_from_ace_bytes(&to_binary(&both), Some(&only_hot)).unwrap();
+        assert_eq!(nuclide.loaded_temperatures, vec!["600"]);
+
+        // Identity is kept when no table is loaded
+        let none: HashSet<String> = ["900".to_string()].into_iter().collect();
+        for bytes in [both.as_bytes().to_vec(), to_binary(&both)] {
+            let nuclide = read_nuclide_from_ace_bytes(&bytes, Some(&none)).unwrap();
+            assert!(nuclide.loaded_temperatures.is_empty());
+            assert_eq!(nuclide.name.as_deref(), Some("Li6"));
+            assert_eq!(nuclide.atomic_number, Some(3));
+            assert_eq!(nuclide.mass_number, Some(6));
+            assert_eq!(nuclide.atomic_weight_ratio, Some(5.9634));
+        }
+
+        let repeated = format!("{}{}", text, text);
+        let error = read_nuclide_from_ace_bytes(repeated.as_bytes(), None).unwrap_err();
+        assert!(error.contains("several tables at 294 K"), "{}", error);
+    }
+
+    #[test]
+    fn test_invalid_tables() {
+        let text = std::fs::read_to_string("tests/Li6.ace").unwrap();
+        let truncated: String = text.lines().take(200).collect::<Vec<_>>().join("\n");
+        let error = read_nuclide_from_ace_bytes(truncated.as_bytes(), None).unwrap_err();
+        assert!(error.contains("ends after"), "{}", error);
+
+        let thermal = text.replacen("3006.00c", "lwtr.20t", 1);
+        let error = read_nuclide_from_ace_bytes(thermal.as_bytes(), None).unwrap_err();
+        assert!(error.contains("not a continuous-energy neutron table"), "{}", error);
+        assert!(read_nuclide_from_ace_bytes(b"", None).is_err());
+    }
+
+    #[test]
+    fn test_table_nuclide_id_am242() {
+        assert_eq!(table_nuclide_id("95242.80c").unwrap().name(), "Am242_m1");
+        assert_eq!(table_nuclide_id("95642.80c").unwrap().name(), "Am242");
+        assert_eq!(table_nuclide_id("1095242.00c").unwrap().name(), "Am242_m1");
+        assert_eq!(table_nuclide_id("92635.80c").unwrap().name(), "U235_m1");
+    }
+
+    #[test]
+    fn test_ace_paths_work_like_json_paths() {
+        assert!(is_ace_file(Path::new("tests/Li6.ace")));
+        assert!(!is_ace_file(Path::new("tests/Li6.json")));
+        assert!(looks_like_ace(b"92235.80c  233.024800"));
+        assert!(looks_like_ace(b"2.0.0  92235.800nc  ENDF"));
+        assert!(!looks_like_ace(b"{\"name\": \"Li6\"}"));
+
+        let nuclide = crate::nuclide::read_nuclide_from_json("tests/Li6.ace", None).unwrap();
+        assert_eq!(nuclide.data_path.as_deref(), Some("tests/Li6.ace"));
+
+        let mut material = crate::Material::new();
+        material.add_nuclide("Li6", 1.0).unwrap();
+        material.set_density("g/cm3", 0.5).unwrap();
+        let mut map = HashMap::new();
+        map.insert("Li6".to_string(), "tests/Li6.ace".to_string());
+        material.read_nuclides_from_json(&map).unwrap();
+        let (xs, energy) = material.macroscopic_cross_section(1);
+        assert!(!xs.is_empty() && xs.len() == energy.len());
+    }
+}
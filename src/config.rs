@@ -1,4 +1,7 @@
 // Global configuration for the materials library
+use crate::element::Element;
+use crate::nuclide_id::NuclideId;
+use crate::nuclide_names::canonical_nuclide_name;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Mutex;
@@ -34,13 +37,16 @@ impl Config {
         }
     }
 
-    /// Set a cross section file path for a nuclide, or set a global default if only a keyword is provided
+    /// Set a cross section file path for a nuclide, or set a global default if only a keyword is provided.
+    ///
+    /// Nuclides may be named in any form accepted by [`crate::parse_nuclide_name`]
+    /// (e.g. "Li-6", "3006") and are stored under their canonical GNDS name.
     pub fn set_cross_section(&mut self, nuclide_or_keyword: &str, path: Option<&str>) {
         // List of acceptable keywords
         const ACCEPTABLE_KEYWORDS: &[&str] = &["tendl-21", "fendl-3.2c"];
         match path {
             Some(p) => {
-                self.cross_sections.insert(nuclide_or_keyword.to_string(), p.to_string());
+                self.cross_sections.insert(canonical_nuclide_name(nuclide_or_keyword), p.to_string());
             }
             None => {
                 // Validate keyword
@@ -54,10 +60,29 @@ impl Config {
 
     /// Get a cross section file path for a nuclide, falling back to the global default if not set
     pub fn get_cross_section(&self, nuclide: &str) -> Option<String> {
-        self.cross_sections.get(nuclide).cloned()
+        self.cross_sections.get(&canonical_nuclide_name(nuclide)).cloned()
             .or_else(|| self.default_cross_section.clone())
     }
 
+    /// Natural element evaluation to use for an element (e.g. `C0` for `"C"`).
+    ///
+    /// Returns the natural element when a cross section file is set for it and
+    /// none of the element's naturally occurring isotopes resolve to data,
+    /// either from their own entries or from the default library keyword.
+    /// [`crate::material::Material::add_element`] then adds the element as
+    /// this nuclide.
+    pub fn natural_element_fallback(&self, element: &str) -> Option<NuclideId> {
+        let natural = NuclideId::natural(element).ok()?;
+        if !self.cross_sections.contains_key(&natural.name()) {
+            return None;
+        }
+        let isotopes = Element::new(element).get_nuclides();
+        if isotopes.iter().any(|isotope| self.get_cross_section(isotope).is_some()) {
+            return None;
+        }
+        Some(natural)
+    }
+
     /// Set multiple cross section file paths at once, or set a global keyword
     pub fn set_cross_sections<T>(&mut self, input: T)
     where
@@ -87,7 +112,7 @@ impl IntoCrossSectionsInput for HashMap<String, String> {
                 // If value is a keyword, set as global default
                 config.default_cross_section = Some(path.clone());
             }
-            config.cross_sections.insert(nuclide, path);
+            config.cross_sections.insert(canonical_nuclide_name(&nuclide), path);
         }
     }
 }
@@ -213,4 +238,36 @@ fn test_set_cross_sections_invalid_string_keyword() {
     let mut config = Config::new();
     config.set_cross_sections("invalid-keyword");
 }
-}
\ No newline at end of file
+
+#[test]
+fn test_natural_element_fallback() {
+    let mut config = Config::new();
+    assert_eq!(config.natural_element_fallback("C"), None);
+    config.set_cross_section("C0", Some("C0.json"));
+    assert_eq!(config.natural_element_fallback("C").unwrap().name(), "C0");
+    config.set_cross_section("C12", Some("C12.json"));
+    assert_eq!(config.natural_element_fallback("C"), None);
+    assert_eq!(config.natural_element_fallback("Xx"), None);
+
+    // Isotopes provided by the default library keyword take precedence
+    let mut config = Config::new();
+    config.set_cross_section("C0", Some("C0.json"));
+    config.set_cross_section("tendl-21", None);
+    assert_eq!(config.natural_element_fallback("C"), None);
+}
+
+#[test]
+fn test_cross_sections_use_canonical_nuclide_names() {
+    let mut config = Config::new();
+    config.set_cross_section("Li-6", Some("Li6.json"));
+    config.set_cross_sections(std::collections::HashMap::from([
+        ("3007.80c".to_string(), "Li7.json".to_string()),
+        ("am242m".to_string(), "Am242_m1.json".to_string()),
+    ]));
+    assert_eq!(config.cross_sections["Li6"], "Li6.json");
+    assert_eq!(config.cross_sections["Li7"], "Li7.json");
+    assert_eq!(config.cross_sections["Am242_m1"], "Am242_m1.json");
+    assert_eq!(config.get_cross_section("li6"), Some("Li6.json".to_string()));
+    assert_eq!(config.get_cross_section("95242"), Some("Am242_m1.json".to_string()));
+}
+}
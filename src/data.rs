@@ -1,3 +1,4 @@
+use crate::nuclide_id::NuclideId;
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
@@ -11,11 +12,9 @@ use std::collections::HashMap;
 pub static ELEMENT_NUCLIDES: Lazy<HashMap<&'static str, Vec<&'static str>>> = Lazy::new(|| {
     let mut map: HashMap<&'static str, Vec<&'static str>> = HashMap::new();
     for &nuclide in NATURAL_ABUNDANCE.keys() {
-        // Find the index where the first digit occurs
-        let idx = nuclide
-            .find(|c: char| c.is_ascii_digit())
-            .unwrap_or(nuclide.len());
-        let element = &nuclide[..idx]; // This is a &'static str slice
+        let element = NuclideId::parse(nuclide)
+            .expect("natural abundance table contains an invalid nuclide name")
+            .symbol();
         map.entry(element).or_insert_with(Vec::new).push(nuclide);
     }
     // Sort nuclides for each element
@@ -502,6 +501,31 @@ pub static NATURAL_ABUNDANCE: Lazy<HashMap<&'static str, f64>> = Lazy::new(|| {
     m
 });
 
+/// Element symbols ordered by atomic number, so `ELEMENT_SYMBOLS[Z - 1]` is
+/// the symbol of the element with atomic number `Z`.
+pub const ELEMENT_SYMBOLS: [&str; 118] = [
+    "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S",
+    "Cl", "Ar", "K", "Ca", "Sc", "Ti", "V", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga",
+    "Ge", "As", "Se", "Br", "Kr", "Rb", "Sr", "Y", "Zr", "Nb", "Mo", "Tc", "Ru", "Rh", "Pd",
+    "Ag", "Cd", "In", "Sn", "Sb", "Te", "I", "Xe", "Cs", "Ba", "La", "Ce", "Pr", "Nd", "Pm",
+    "Sm", "Eu", "Gd", "Tb", "Dy", "Ho", "Er", "Tm", "Yb", "Lu", "Hf", "Ta", "W", "Re", "Os",
+    "Ir", "Pt", "Au", "Hg", "Tl", "Pb", "Bi", "Po", "At", "Rn", "Fr", "Ra", "Ac", "Th", "Pa",
+    "U", "Np", "Pu", "Am", "Cm", "Bk", "Cf", "Es", "Fm", "Md", "No", "Lr", "Rf", "Db", "Sg",
+    "Bh", "Hs", "Mt", "Ds", "Rg", "Cn", "Nh", "Fl", "Mc", "Lv", "Ts", "Og",
+];
+
+/// Neutron mass in atomic mass units (CODATA 2018), used to convert atomic
+/// weight ratios to atomic masses.
+pub const NEUTRON_MASS: f64 = 1.00866491595;
+
+/// Atomic number of an element from its symbol (e.g. `"Fe"` -> 26).
+pub(crate) fn atomic_number(symbol: &str) -> Option<u32> {
+    ELEMENT_SYMBOLS
+        .iter()
+        .position(|&s| s == symbol)
+        .map(|index| index as u32 + 1)
+}
+
 /// Mapping from element symbol to its lowercase English name.
 ///
 /// Provided for convenience when presenting user‑facing descriptions and for
@@ -4186,6 +4210,33 @@ pub static ATOMIC_MASSES: Lazy<HashMap<&'static str, f64>> = Lazy::new(|| {
     m
 });
 
+/// Look up the atomic mass (g/mol) of a nuclide in [`ATOMIC_MASSES`].
+///
+/// Metastable states such as `Ta180_m1` fall back to the mass of the ground
+/// state, and natural elements such as `C0` use the abundance weighted mass of
+/// the element's natural isotopes.
+pub(crate) fn atomic_mass(nuclide: &str) -> Option<f64> {
+    if let Some(&mass) = ATOMIC_MASSES.get(nuclide) {
+        return Some(mass);
+    }
+    let id = NuclideId::parse(nuclide).ok()?;
+    if !id.is_natural() {
+        return ATOMIC_MASSES.get(id.ground_state().name().as_str()).copied();
+    }
+    let mut total_abundance = 0.0;
+    let mut total_mass = 0.0;
+    for isotope in ELEMENT_NUCLIDES.get(id.symbol())? {
+        let abundance = NATURAL_ABUNDANCE[isotope];
+        total_abundance += abundance;
+        total_mass += abundance * atomic_mass(isotope)?;
+    }
+    if total_abundance > 0.0 {
+        Some(total_mass / total_abundance)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4218,6 +4269,32 @@ mod tests {
         let be_nuclides = ELEMENT_NUCLIDES.get("Be").unwrap();
         assert_eq!(be_nuclides, &vec!["Be9"]);
     }
+
+    #[test]
+    fn test_atomic_number() {
+        assert_eq!(atomic_number("H"), Some(1));
+        assert_eq!(atomic_number("Fe"), Some(26));
+        assert_eq!(atomic_number("U"), Some(92));
+        assert_eq!(atomic_number("Og"), Some(118));
+        assert_eq!(atomic_number("Xx"), None);
+        for symbol in ELEMENT_NAMES.keys() {
+            assert!(atomic_number(symbol).is_some(), "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn test_atomic_mass_metastable_and_natural() {
+        assert_eq!(atomic_mass("Am242_m1"), atomic_mass("Am242"));
+        assert!(atomic_mass("Am242").is_some());
+        let li6 = NATURAL_ABUNDANCE["Li6"] * ATOMIC_MASSES["Li6"];
+        let li7 = NATURAL_ABUNDANCE["Li7"] * ATOMIC_MASSES["Li7"];
+        let expected = (li6 + li7) / (NATURAL_ABUNDANCE["Li6"] + NATURAL_ABUNDANCE["Li7"]);
+        assert!((atomic_mass("Li0").unwrap() - expected).abs() < 1e-12);
+        assert!((atomic_mass("C0").unwrap() - 12.011).abs() < 1e-3);
+        assert_eq!(atomic_mass("Tc0"), None);
+        assert_eq!(atomic_mass("Xx1"), None);
+        assert_eq!(ELEMENT_NUCLIDES["Ta"], vec!["Ta180_m1", "Ta181"]);
+    }
 }
 
 /// A static HashMap that maps ENDF MT reaction numbers to their descriptive names
@@ -4400,3 +4477,30 @@ pub static REACTION_MT: Lazy<HashMap<&'static str, i32>> = Lazy::new(|| {
     ].iter().cloned().collect()
 });
 
+
+/// ENDF summation rules mapping each redundant MT to the MTs it is the sum of.
+/// Components may themselves be redundant (e.g. MT 1 = MT 2 + MT 3).
+pub static SUM_RULES: Lazy<HashMap<i32, Vec<i32>>> = Lazy::new(|| {
+    let mut rules = HashMap::new();
+    rules.insert(1, vec![2, 3]);
+    let mut nonelastic = vec![4, 5, 11, 16, 17, 22, 23, 24, 25, 27, 28, 29, 30, 32, 33, 34, 35, 36, 37, 41, 42, 44, 45];
+    nonelastic.extend([152, 153, 154]);
+    nonelastic.extend(156..=181);
+    nonelastic.extend(183..=190);
+    nonelastic.extend([194, 195, 196, 198, 199, 200]);
+    rules.insert(3, nonelastic);
+    rules.insert(4, (50..=91).collect());
+    rules.insert(16, (875..=891).collect());
+    rules.insert(18, vec![19, 20, 21, 38]);
+    rules.insert(27, vec![18, 101]);
+    let mut disappearance: Vec<i32> = (102..=109).collect();
+    disappearance.extend(111..=117);
+    disappearance.extend([155, 182, 191, 192, 193, 197]);
+    rules.insert(101, disappearance);
+    rules.insert(103, (600..=649).collect());
+    rules.insert(104, (650..=699).collect());
+    rules.insert(105, (700..=749).collect());
+    rules.insert(106, (750..=799).collect());
+    rules.insert(107, (800..=849).collect());
+    rules
+});
@@ -39,3 +39,51 @@ pub fn atomic_masses(py: Python) -> PyObject {
     }
     dict.into()
 }
+
+/// Canonical GNDS name of a nuclide given in any common form.
+///
+/// Args:
+///     name (str): Nuclide name such as "Li-6", "li6", "3006.80c" or "U235m".
+///
+/// Returns:
+///     str: The canonical name (e.g. "Li6", "U235_m1").
+///
+/// Raises:
+///     ValueError: If the name cannot be interpreted as a nuclide.
+#[pyfunction]
+#[pyo3(name = "to_gnds_name")]
+pub fn py_to_gnds_name(name: &str) -> PyResult<String> {
+    crate::nuclide_names::to_gnds_name(name).map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+/// ZAID of a nuclide, with metastable states as Z * 1000 + A + 300 + 100 * m.
+///
+/// Args:
+///     name (str): Nuclide name in any form accepted by to_gnds_name.
+///
+/// Returns:
+///     int: The ZAID (e.g. 3006 for "Li6", 95242 for "Am242_m1").
+///
+/// Raises:
+///     ValueError: If the name cannot be interpreted as a nuclide.
+#[pyfunction]
+#[pyo3(name = "to_zaid")]
+pub fn py_to_zaid(name: &str) -> PyResult<u32> {
+    crate::nuclide_names::to_zaid(name).map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+/// Hyphenated name of a nuclide (e.g. "Li-6", "Am-242m1" or "C-nat").
+///
+/// Args:
+///     name (str): Nuclide name in any form accepted by to_gnds_name.
+///
+/// Returns:
+///     str: The hyphenated name.
+///
+/// Raises:
+///     ValueError: If the name cannot be interpreted as a nuclide.
+#[pyfunction]
+#[pyo3(name = "to_hyphenated_name")]
+pub fn py_to_hyphenated_name(name: &str) -> PyResult<String> {
+    crate::nuclide_names::to_hyphenated_name(name).map_err(pyo3::exceptions::PyValueError::new_err)
+}
@@ -39,3 +39,21 @@ pub fn atomic_masses() -> JsValue {
         .collect();
     to_value(&map).unwrap()
 }
+
+/// Canonical GNDS name of a nuclide given in any common form (e.g. "Li-6" -> "Li6").
+#[wasm_bindgen(js_name = to_gnds_name)]
+pub fn wasm_to_gnds_name(name: &str) -> Result<String, JsValue> {
+    crate::nuclide_names::to_gnds_name(name).map_err(|e| JsValue::from_str(&e))
+}
+
+/// ZAID of a nuclide, with metastable states as Z * 1000 + A + 300 + 100 * m.
+#[wasm_bindgen(js_name = to_zaid)]
+pub fn wasm_to_zaid(name: &str) -> Result<u32, JsValue> {
+    crate::nuclide_names::to_zaid(name).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Hyphenated name of a nuclide (e.g. "Li-6", "Am-242m1" or "C-nat").
+#[wasm_bindgen(js_name = to_hyphenated_name)]
+pub fn wasm_to_hyphenated_name(name: &str) -> Result<String, JsValue> {
+    crate::nuclide_names::to_hyphenated_name(name).map_err(|e| JsValue::from_str(&e))
+}
@@ -0,0 +1,172 @@
+// Provides exact Doppler broadening of pointwise cross sections (SIGMA1 method)
+use std::f64::consts::PI;
+
+/// Boltzmann constant in eV/K
+pub(crate) const BOLTZMANN: f64 = 8.617333262e-5;
+
+/// Half width of the broadening kernel in reduced velocity units. Contributions
+/// beyond it are below exp(-25) relative to the cross section.
+const CUTOFF: f64 = 5.0;
+
+/// Doppler broaden a lin-lin pointwise cross section by `delta_temperature` K.
+///
+/// Uses the SIGMA1 free gas kernel, integrating the piecewise linear cross
+/// section exactly in the reduced velocity `x = sqrt(alpha * E)` with
+/// `alpha = awr / (k * delta_temperature)`. The result is evaluated on the
+/// same energy grid. Below the first energy the cross section is taken as 1/v
+/// when `one_over_v` is set (data starting at the bottom of the grid) and zero
+/// otherwise (data starting at a threshold); above the last energy it is
+/// constant.
+pub(crate) fn broaden(
+    energy: &[f64],
+    cross_section: &[f64],
+    awr: f64,
+    delta_temperature: f64,
+    one_over_v: bool,
+) -> Vec<f64> {
+    if energy.is_empty() || delta_temperature <= 0.0 {
+        return cross_section.to_vec();
+    }
+    let alpha = awr / (BOLTZMANN * delta_temperature);
+    let x: Vec<f64> = energy.iter().map(|e| (alpha * e).sqrt()).collect();
+    let n = x.len();
+
+    (0..n)
+        .map(|i| {
+            let y = x[i];
+            if y <= 0.0 {
+                return cross_section[i];
+            }
+            let mut total = 0.0;
+            // exp(-(x - y)^2) term, then the exp(-(x + y)^2) term near zero velocity
+            for (shift, sign) in [(y, 1.0), (-y, -1.0)] {
+                let low = (shift - CUTOFF).max(0.0);
+                let high = shift + CUTOFF;
+                if high <= low {
+                    continue;
+                }
+                let mut sum = 0.0;
+
+                // Below the first point
+                if one_over_v && low < x[0] {
+                    let c = cross_section[0] * x[0];
+                    let m = moments(low - shift, x[0].min(high) - shift);
+                    sum += c * shift * m[0] + c * m[1];
+                }
+
+                // Interval k spans x[k]..x[k+1]
+                let start = x.partition_point(|&xk| xk <= low).saturating_sub(1);
+                for k in start..n - 1 {
+                    if x[k] >= high {
+                        break;
+                    }
+                    let (a, b) = (x[k].max(low), x[k + 1].min(high));
+                    if b <= a {
+                        continue;
+                    }
+                    let slope = (cross_section[k + 1] - cross_section[k])
+                        / ((x[k + 1] - x[k]) * (x[k + 1] + x[k]));
+                    // Cross section as a quadratic in z = x - shift
+                    let c0 = cross_section[k] + slope * (shift - x[k]) * (shift + x[k]);
+                    let c1 = 2.0 * shift * slope;
+                    let c2 = slope;
+                    sum += quadratic_term(shift, [c0, c1, c2], &moments(a - shift, b - shift));
+                }
+
+                // Constant above the last point
+                if high > x[n - 1] {
+                    let a = x[n - 1].max(low);
+                    let m = moments(a - shift, high - shift);
+                    sum += quadratic_term(shift, [cross_section[n - 1], 0.0, 0.0], &m);
+                }
+                total += sign * sum;
+            }
+            (total / (PI.sqrt() * y * y)).max(0.0)
+        })
+        .collect()
+}
+
+/// Integral of `(shift + z)^2 * (c0 + c1 z + c2 z^2)` times exp(-z^2) given the
+/// moments of exp(-z^2) over the integration range.
+fn quadratic_term(shift: f64, c: [f64; 3], m: &[f64; 5]) -> f64 {
+    let [c0, c1, c2] = c;
+    let s2 = shift * shift;
+    s2 * c0 * m[0]
+        + (s2 * c1 + 2.0 * shift * c0) * m[1]
+        + (s2 * c2 + 2.0 * shift * c1 + c0) * m[2]
+        + (2.0 * shift * c2 + c1) * m[3]
+        + c2 * m[4]
+}
+
+/// Moments `integral of z^n exp(-z^2) dz` from `a` to `b` for n = 0..=4.
+fn moments(a: f64, b: f64) -> [f64; 5] {
+    let fa = tail_moments(a);
+    let fb = tail_moments(b);
+    [fa[0] - fb[0], fa[1] - fb[1], fa[2] - fb[2], fa[3] - fb[3], fa[4] - fb[4]]
+}
+
+/// Upper tail moments `integral of z^n exp(-z^2) dz` from `a` to infinity.
+fn tail_moments(a: f64) -> [f64; 5] {
+    let e = (-a * a).exp();
+    let f0 = 0.5 * PI.sqrt() * libm::erfc(a);
+    let f1 = 0.5 * e;
+    let f2 = 0.5 * f0 + 0.5 * a * e;
+    let f3 = f1 + 0.5 * a * a * e;
+    let f4 = 1.5 * f2 + 0.5 * a * a * a * e;
+    [f0, f1, f2, f3, f4]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_grid(low: f64, high: f64, points: usize) -> Vec<f64> {
+        (0..points)
+            .map(|i| low * (high / low).powf(i as f64 / (points - 1) as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_constant_cross_section() {
+        // Exact result for a constant cross section extending to zero energy
+        let energy = log_grid(1e-8, 1e3, 600);
+        let mut energy_from_zero = vec![0.0];
+        energy_from_zero.extend(&energy);
+        let xs = vec![10.0; energy_from_zero.len()];
+        let (awr, dt) = (10.0, 600.0);
+        let broadened = broaden(&energy_from_zero, &xs, awr, dt, false);
+        let alpha = awr / (BOLTZMANN * dt);
+        for (e, sigma) in energy_from_zero.iter().zip(&broadened).skip(1).step_by(23) {
+            let y = (alpha * e).sqrt();
+            let exact = 10.0
+                * ((1.0 + 0.5 / (y * y)) * libm::erf(y) + (-y * y).exp() / (PI.sqrt() * y));
+            assert!((sigma - exact).abs() < 1e-6 * exact, "E={} got {} expected {}", e, sigma, exact);
+        }
+    }
+
+    #[test]
+    fn test_one_over_v_is_unchanged() {
+        let energy = log_grid(1e-5, 1e2, 1500);
+        let xs: Vec<f64> = energy.iter().map(|e| 5.0 / e.sqrt()).collect();
+        let broadened = broaden(&energy, &xs, 1.0, 900.0, true);
+        // Ignore the top of the grid, where the constant extrapolation differs
+        for i in (0..1100).step_by(25) {
+            assert!((broadened[i] - xs[i]).abs() < 1e-4 * xs[i], "E={} got {} expected {}", energy[i], broadened[i], xs[i]);
+        }
+    }
+
+    #[test]
+    fn test_resonance_is_broadened_and_area_kept() {
+        // A narrow triangular resonance gets lower and wider
+        let energy: Vec<f64> = (0..2001).map(|i| 90.0 + 0.01 * i as f64).collect();
+        let xs: Vec<f64> = energy
+            .iter()
+            .map(|e| 1.0 + 1000.0 * (1.0 - (e - 100.0).abs() / 0.2).max(0.0))
+            .collect();
+        let broadened = broaden(&energy, &xs, 200.0, 300.0, false);
+        let peak = broadened.iter().cloned().fold(0.0, f64::max);
+        assert!(peak < 1001.0 && peak > 1.0);
+        let area = |v: &[f64]| v.windows(2).map(|w| 0.5 * (w[0] + w[1]) * 0.01).sum::<f64>();
+        assert!((area(&broadened) - area(&xs)).abs() < 0.01 * area(&xs));
+    }
+}
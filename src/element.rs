@@ -1,5 +1,6 @@
 // Provides functionality for working with natural elements and their isotopic abundances
-use crate::data::ELEMENT_NUCLIDES;
+use crate::data::{atomic_mass, ELEMENT_NUCLIDES, NATURAL_ABUNDANCE};
+use crate::material::FractionType;
 
 /// Represents a chemical element identified by its symbol (e.g. `"Fe"`).
 ///
@@ -23,6 +24,122 @@ impl Element {
             .map(|v| v.iter().map(|s| s.to_string()).collect())
             .unwrap_or_default()
     }
+
+    /// Return the naturally occurring isotopes with their atom abundances
+    /// (e.g. `[("Li6", 0.07589), ("Li7", 0.92411)]`).
+    pub fn get_natural_abundances(&self) -> Vec<(String, f64)> {
+        ELEMENT_NUCLIDES
+            .get(self.name.as_str())
+            .map(|isotopes| {
+                isotopes
+                    .iter()
+                    .filter_map(|&isotope| {
+                        NATURAL_ABUNDANCE
+                            .get(isotope)
+                            .filter(|&&abundance| abundance > 0.0)
+                            .map(|&abundance| (isotope.to_string(), abundance))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return the isotope atom abundances with one isotope enriched.
+    ///
+    /// `enrichment` is the percentage (0-100) of `enrichment_target` in the
+    /// element, either as atom or weight percent depending on `enrichment_type`.
+    /// The remaining isotopes keep their natural proportions relative to each
+    /// other and share the rest of the element. Returned abundances are atom
+    /// fractions summing to one.
+    pub fn get_enriched_abundances(
+        &self,
+        enrichment_target: &str,
+        enrichment: f64,
+        enrichment_type: FractionType,
+    ) -> Result<Vec<(String, f64)>, String> {
+        if !(0.0..=100.0).contains(&enrichment) {
+            return Err(format!(
+                "Enrichment must be between 0 and 100 percent, got {}",
+                enrichment
+            ));
+        }
+        let natural = self.get_natural_abundances();
+        if natural.is_empty() {
+            return Err(format!(
+                "Element '{}' not found in the natural abundance database",
+                self.name
+            ));
+        }
+        if !self.get_nuclides().iter().any(|n| n == enrichment_target) {
+            return Err(format!(
+                "Enrichment target '{}' is not an isotope of element '{}'",
+                enrichment_target, self.name
+            ));
+        }
+
+        let target_fraction = enrichment / 100.0;
+        // Work in atoms or in mass depending on the enrichment type
+        let mut others: Vec<(String, f64)> = Vec::new();
+        for (isotope, abundance) in natural {
+            if isotope == enrichment_target {
+                continue;
+            }
+            let weight = match enrichment_type {
+                FractionType::Atom => abundance,
+                FractionType::Weight => abundance * isotope_mass(&isotope)?,
+            };
+            others.push((isotope, weight));
+        }
+        let others_total: f64 = others.iter().map(|(_, w)| w).sum();
+        if others_total <= 0.0 && target_fraction < 1.0 {
+            return Err(format!(
+                "Element '{}' has no other natural isotopes to balance the enrichment of '{}'",
+                self.name, enrichment_target
+            ));
+        }
+
+        let mut fractions = vec![(enrichment_target.to_string(), target_fraction)];
+        for (isotope, weight) in others {
+            fractions.push((isotope, (1.0 - target_fraction) * weight / others_total));
+        }
+
+        // Convert weight fractions to atom fractions
+        if enrichment_type == FractionType::Weight {
+            let mut moles = Vec::with_capacity(fractions.len());
+            for (isotope, weight) in &fractions {
+                moles.push(weight / isotope_mass(isotope)?);
+            }
+            let total_moles: f64 = moles.iter().sum();
+            for ((_, fraction), mol) in fractions.iter_mut().zip(moles) {
+                *fraction = mol / total_moles;
+            }
+        }
+        fractions.retain(|(_, f)| *f > 0.0);
+        Ok(fractions)
+    }
+
+    /// Natural atomic mass (g/mol) of the element, averaged over its isotopes by abundance.
+    pub fn get_atomic_mass(&self) -> Result<f64, String> {
+        let natural = self.get_natural_abundances();
+        if natural.is_empty() {
+            return Err(format!(
+                "Element '{}' not found in the natural abundance database",
+                self.name
+            ));
+        }
+        let mut mass_sum = 0.0;
+        let mut abundance_sum = 0.0;
+        for (isotope, abundance) in &natural {
+            mass_sum += abundance * isotope_mass(isotope)?;
+            abundance_sum += abundance;
+        }
+        Ok(mass_sum / abundance_sum)
+    }
+}
+
+fn isotope_mass(isotope: &str) -> Result<f64, String> {
+    atomic_mass(isotope)
+        .ok_or_else(|| format!("Atomic mass for nuclide '{}' not found in the database", isotope))
 }
 
 #[cfg(test)]
@@ -62,4 +179,77 @@ mod tests {
             "Fe isotope list mismatch"
         );
     }
+
+    #[test]
+    fn test_natural_abundances_li() {
+        let li = Element::new("Li");
+        assert_eq!(
+            li.get_natural_abundances(),
+            vec![("Li6".to_string(), 0.07589), ("Li7".to_string(), 0.92411)]
+        );
+        assert!(Element::new("Xx").get_natural_abundances().is_empty());
+    }
+
+    #[test]
+    fn test_enriched_abundances_atom_percent() {
+        let li = Element::new("Li");
+        let abundances = li
+            .get_enriched_abundances("Li6", 60.0, FractionType::Atom)
+            .unwrap();
+        assert_eq!(abundances[0], ("Li6".to_string(), 0.6));
+        assert!((abundances[1].1 - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_enriched_abundances_weight_percent() {
+        let li = Element::new("Li");
+        let abundances: std::collections::HashMap<String, f64> = li
+            .get_enriched_abundances("Li6", 90.0, FractionType::Weight)
+            .unwrap()
+            .into_iter()
+            .collect();
+        let m6 = atomic_mass("Li6").unwrap();
+        let m7 = atomic_mass("Li7").unwrap();
+        let mass6 = abundances["Li6"] * m6;
+        let mass7 = abundances["Li7"] * m7;
+        assert!((mass6 / (mass6 + mass7) - 0.9).abs() < 1e-12);
+        assert!((abundances["Li6"] + abundances["Li7"] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_enriched_abundances_more_than_two_isotopes() {
+        // Enrich Fe57 to 50 at.%, other iron isotopes keep their natural ratios
+        let fe = Element::new("Fe");
+        let abundances: std::collections::HashMap<String, f64> = fe
+            .get_enriched_abundances("Fe57", 50.0, FractionType::Atom)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(abundances.len(), 4);
+        assert!((abundances["Fe57"] - 0.5).abs() < 1e-12);
+        let natural_ratio = NATURAL_ABUNDANCE["Fe54"] / NATURAL_ABUNDANCE["Fe56"];
+        assert!((abundances["Fe54"] / abundances["Fe56"] - natural_ratio).abs() < 1e-12);
+        let total: f64 = abundances.values().sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_enriched_abundances_invalid() {
+        let li = Element::new("Li");
+        assert!(li.get_enriched_abundances("B10", 50.0, FractionType::Atom).is_err());
+        assert!(li.get_enriched_abundances("Li6", 120.0, FractionType::Atom).is_err());
+        assert!(li.get_enriched_abundances("Li6", -1.0, FractionType::Atom).is_err());
+        // A single isotope element cannot be depleted
+        let be = Element::new("Be");
+        assert!(be.get_enriched_abundances("Be9", 50.0, FractionType::Atom).is_err());
+        assert!(be.get_enriched_abundances("Be9", 100.0, FractionType::Atom).is_ok());
+    }
+
+    #[test]
+    fn test_element_atomic_mass() {
+        let li = Element::new("Li");
+        let expected = 0.07589 * atomic_mass("Li6").unwrap() + 0.92411 * atomic_mass("Li7").unwrap();
+        assert!((li.get_atomic_mass().unwrap() - expected).abs() < 1e-9);
+        assert!(Element::new("Xx").get_atomic_mass().is_err());
+    }
 }
@@ -0,0 +1,159 @@
+// Provides conversion of materials to FISPACT-II inventory input (MASS and FUEL)
+use crate::material::Material;
+use crate::nuclide_id::NuclideId;
+use std::collections::BTreeMap;
+
+/// FISPACT-II name of a nuclide: metastable states use the `m`, `n` suffixes
+/// (e.g. `"Am242_m1"` becomes `"Am242m"`).
+fn fispact_nuclide_name(nuclide: &str) -> Result<String, String> {
+    let id = NuclideId::parse(nuclide)?;
+    if id.is_natural() {
+        return Err(format!(
+            "Nuclide '{}' is a natural element evaluation, FISPACT-II FUEL input needs isotopes",
+            nuclide
+        ));
+    }
+    let ground = id.ground_state().name();
+    match id.metastable {
+        0 => Ok(ground),
+        1 => Ok(format!("{}m", ground)),
+        2 => Ok(format!("{}n", ground)),
+        _ => Err(format!(
+            "Nuclide '{}' has no FISPACT-II name, only first and second metastable states are supported",
+            nuclide
+        )),
+    }
+}
+
+/// FISPACT-II comments listing the impurities of a material.
+fn impurity_comments(material: &Material) -> String {
+    material
+        .get_impurities()
+        .iter()
+        .map(|impurity| format!("<< impurity {} >>\n", impurity))
+        .collect()
+}
+
+/// Write the FISPACT-II `DENSITY` and `MASS` input for a material.
+///
+/// The total mass in kg is the material volume (cm3) times its mass density,
+/// followed by the weight percentage of each element. FISPACT-II uses natural
+/// isotopic compositions for `MASS`, so use [`material_to_fispact_fuel`] for
+/// enriched or depleted materials.
+pub fn material_to_fispact_mass(material: &Material) -> Result<String, String> {
+    let total_mass = material.get_total_mass()?;
+    let mass_fractions: BTreeMap<String, f64> =
+        material.get_element_mass_fractions()?.into_iter().collect();
+
+    let mut text = impurity_comments(material);
+    text.push_str(&format!(
+        "DENSITY {}\nMASS {} {}\n",
+        material.get_mass_density()?,
+        total_mass * 1.0e-3,
+        mass_fractions.len()
+    ));
+    for (element, fraction) in mass_fractions {
+        text.push_str(&format!("  {:<3} {}\n", element, 100.0 * fraction));
+    }
+    Ok(text)
+}
+
+/// Write the FISPACT-II `DENSITY` and `FUEL` input for a material.
+///
+/// Lists the number of atoms of each nuclide in the material volume, which
+/// keeps the exact isotopic composition.
+pub fn material_to_fispact_fuel(material: &Material) -> Result<String, String> {
+    let atoms: BTreeMap<String, f64> = material.get_atoms_per_nuclide()?.into_iter().collect();
+    let mut text = impurity_comments(material);
+    text.push_str(&format!(
+        "DENSITY {}\nFUEL {}\n",
+        material.get_mass_density()?,
+        atoms.len()
+    ));
+    for (nuclide, count) in atoms {
+        text.push_str(&format!(
+            "  {:<8} {:e}\n",
+            fispact_nuclide_name(&nuclide)?,
+            count
+        ));
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::FractionType;
+
+    fn steel() -> Material {
+        let mut material = Material::new();
+        material
+            .add_element_with_fraction_type("Fe", 0.98, FractionType::Weight)
+            .unwrap();
+        material
+            .add_element_with_fraction_type("C", 0.02, FractionType::Weight)
+            .unwrap();
+        material.set_density("g/cm3", 8.0).unwrap();
+        material.volume(Some(500.0)).unwrap();
+        material
+    }
+
+    #[test]
+    fn test_material_to_fispact_mass() {
+        let text = material_to_fispact_mass(&steel()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "DENSITY 8");
+        assert_eq!(lines[1], "MASS 4 2");
+        assert!(lines[2].starts_with("  C   "));
+        assert!(lines[3].starts_with("  Fe  "));
+        let carbon: f64 = lines[2].split_whitespace().nth(1).unwrap().parse().unwrap();
+        let iron: f64 = lines[3].split_whitespace().nth(1).unwrap().parse().unwrap();
+        assert!((carbon - 2.0).abs() < 1e-9);
+        assert!((iron - 98.0).abs() < 1e-9);
+
+        let mut no_volume = steel();
+        no_volume.volume = None;
+        assert!(material_to_fispact_mass(&no_volume).is_err());
+    }
+
+    #[test]
+    fn test_material_to_fispact_fuel() {
+        let mut material = Material::new();
+        material.add_nuclide("Li6", 0.5).unwrap();
+        material.add_nuclide("Li7", 0.5).unwrap();
+        material.set_density("atom/b-cm", 0.04).unwrap();
+        material.volume(Some(10.0)).unwrap();
+        let text = material_to_fispact_fuel(&material).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[1], "FUEL 2");
+        assert_eq!(lines[2], "  Li6      2e23");
+        assert_eq!(lines[3], "  Li7      2e23");
+
+        let mut no_volume = material.clone();
+        no_volume.volume = None;
+        assert!(material_to_fispact_fuel(&no_volume).is_err());
+    }
+
+    #[test]
+    fn test_fispact_impurity_comments() {
+        let mut material = steel();
+        material
+            .add_impurity("Co", 50.0, FractionType::Weight)
+            .unwrap();
+        let text = material_to_fispact_mass(&material).unwrap();
+        assert!(text.starts_with("<< impurity Co 50 wppm >>\nDENSITY 8\nMASS 4 3\n"));
+        assert!(text.contains("  Co  "));
+        let text = material_to_fispact_fuel(&material).unwrap();
+        assert!(text.starts_with("<< impurity Co 50 wppm >>\n"));
+    }
+
+    #[test]
+    fn test_fispact_nuclide_name() {
+        assert_eq!(fispact_nuclide_name("Fe56").unwrap(), "Fe56");
+        assert_eq!(fispact_nuclide_name("Am242_m1").unwrap(), "Am242m");
+        assert_eq!(fispact_nuclide_name("Hf178_m2").unwrap(), "Hf178n");
+        assert!(fispact_nuclide_name("Xx1_m3").is_err());
+        assert!(fispact_nuclide_name("Hf178_m3").is_err());
+        assert!(fispact_nuclide_name("C0").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+// Provides parsing of chemical formulas into element amounts
+use crate::data::ELEMENT_NAMES;
+
+/// Parse a chemical formula into element symbols and their amounts.
+///
+/// The formula may contain:
+/// * element symbols with optional integer or fractional subscripts
+///   (`"Li4SiO4"`, `"Pb84.2Li15.8"`),
+/// * parenthesised or bracketed groups with a multiplier (`"Ca3(PO4)2"`),
+/// * hydrates separated by `·`, `•` or `*`, optionally with a leading
+///   coefficient (`"CaSO4·2H2O"`). A `.` is also accepted as hydrate separator
+///   when it is not between two digits, as it is otherwise a decimal point.
+///
+/// A `.` between a subscript and digits followed by an element or group, as
+/// in `"CaSO4.2H2O"`, could be either. It is read as a decimal point when the
+/// formula has other fractional subscripts (`"Pb84.2Li15.8"`) and is an error
+/// otherwise.
+///
+/// Amounts of repeated elements are summed and the elements are returned in
+/// order of first appearance.
+pub fn parse_formula(formula: &str) -> Result<Vec<(String, f64)>, String> {
+    if formula.trim().is_empty() {
+        return Err(String::from("Chemical formula cannot be empty"));
+    }
+    let chars: Vec<char> = formula.chars().collect();
+
+    let mut amounts: Vec<(String, f64)> = Vec::new();
+    for segment in split_hydrate(&chars, formula)? {
+        let segment: Vec<char> = segment.into_iter().filter(|c| !c.is_whitespace()).collect();
+        if segment.is_empty() {
+            return Err(format!(
+                "Chemical formula '{}' contains an empty hydrate component",
+                formula
+            ));
+        }
+        let mut parser = FormulaParser {
+            chars: segment,
+            pos: 0,
+            formula,
+        };
+        let coefficient = parser.parse_number()?.unwrap_or(1.0);
+        let group = parser.parse_group(None)?;
+        if group.is_empty() {
+            return Err(format!(
+                "Chemical formula '{}' contains no elements",
+                formula
+            ));
+        }
+        for (symbol, amount) in group {
+            add_amount(&mut amounts, symbol, amount * coefficient);
+        }
+    }
+    Ok(amounts)
+}
+
+/// Split a formula into its hydrate components.
+fn split_hydrate(chars: &[char], formula: &str) -> Result<Vec<Vec<char>>, String> {
+    let is_decimal_point = |i: usize| {
+        chars[i] == '.'
+            && i > 0
+            && chars[i - 1].is_ascii_digit()
+            && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())
+    };
+    // Decimal points whose digits are followed by an element or group could
+    // also separate a hydrate with a leading coefficient
+    let is_ambiguous = |i: usize| {
+        chars[i + 1..]
+            .iter()
+            .find(|c| !c.is_ascii_digit() && !c.is_whitespace())
+            .is_some_and(|&c| c.is_ascii_uppercase() || c == '(' || c == '[')
+    };
+    let decimal_points: Vec<usize> = (0..chars.len()).filter(|&i| is_decimal_point(i)).collect();
+    if let Some(&ambiguous) = decimal_points.iter().find(|&&i| is_ambiguous(i)) {
+        if decimal_points.iter().all(|&i| is_ambiguous(i)) {
+            let start = chars[..ambiguous]
+                .iter()
+                .rposition(|c| !c.is_ascii_digit())
+                .map_or(0, |p| p + 1);
+            let end = chars[ambiguous + 1..]
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .map_or(chars.len(), |p| ambiguous + 2 + p);
+            return Err(format!(
+                "Chemical formula '{}' is ambiguous: the '.' in '{}' may be a decimal point or a hydrate separator, use '·' or '*' to separate hydrates (e.g. 'CaSO4·2H2O')",
+                formula,
+                chars[start..end].iter().collect::<String>()
+            ));
+        }
+    }
+
+    let mut segments = vec![Vec::new()];
+    for (i, &c) in chars.iter().enumerate() {
+        let is_separator = match c {
+            '·' | '•' | '*' => true,
+            '.' => !is_decimal_point(i),
+            _ => false,
+        };
+        if is_separator {
+            segments.push(Vec::new());
+        } else {
+            segments.last_mut().unwrap().push(c);
+        }
+    }
+    Ok(segments)
+}
+
+/// Add an amount to an element, keeping the order of first appearance.
+fn add_amount(amounts: &mut Vec<(String, f64)>, symbol: String, amount: f64) {
+    match amounts.iter_mut().find(|(s, _)| *s == symbol) {
+        Some((_, existing)) => *existing += amount,
+        None => amounts.push((symbol, amount)),
+    }
+}
+
+struct FormulaParser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    formula: &'a str,
+}
+
+impl FormulaParser<'_> {
+    /// Parse elements and groups until the closing bracket (or the end of input).
+    fn parse_group(&mut self, closing: Option<char>) -> Result<Vec<(String, f64)>, String> {
+        let mut amounts: Vec<(String, f64)> = Vec::new();
+        loop {
+            let c = match self.chars.get(self.pos) {
+                Some(&c) => c,
+                None => {
+                    if let Some(close) = closing {
+                        return Err(format!(
+                            "Unbalanced brackets in chemical formula '{}': missing '{}'",
+                            self.formula, close
+                        ));
+                    }
+                    break;
+                }
+            };
+
+            if Some(c) == closing {
+                self.pos += 1;
+                break;
+            }
+
+            match c {
+                '(' | '[' => {
+                    self.pos += 1;
+                    let close = if c == '(' { ')' } else { ']' };
+                    let inner = self.parse_group(Some(close))?;
+                    if inner.is_empty() {
+                        return Err(format!(
+                            "Empty group in chemical formula '{}'",
+                            self.formula
+                        ));
+                    }
+                    let multiplier = self.parse_number()?.unwrap_or(1.0);
+                    for (symbol, amount) in inner {
+                        add_amount(&mut amounts, symbol, amount * multiplier);
+                    }
+                }
+                ')' | ']' => {
+                    return Err(format!(
+                        "Unbalanced brackets in chemical formula '{}': unexpected '{}'",
+                        self.formula, c
+                    ));
+                }
+                c if c.is_ascii_uppercase() => {
+                    let symbol = self.parse_symbol()?;
+                    let count = self.parse_number()?.unwrap_or(1.0);
+                    add_amount(&mut amounts, symbol, count);
+                }
+                other => {
+                    return Err(format!(
+                        "Unexpected character '{}' in chemical formula '{}'",
+                        other, self.formula
+                    ));
+                }
+            }
+        }
+        Ok(amounts)
+    }
+
+    /// Parse an element symbol: an uppercase letter followed by lowercase letters.
+    fn parse_symbol(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        while self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_lowercase())
+        {
+            self.pos += 1;
+        }
+        let symbol: String = self.chars[start..self.pos].iter().collect();
+        if ELEMENT_NAMES.contains_key(symbol.as_str()) {
+            Ok(symbol)
+        } else {
+            Err(format!(
+                "Element '{}' in chemical formula '{}' is not a recognized element symbol",
+                symbol, self.formula
+            ))
+        }
+    }
+
+    /// Parse an optional integer or decimal number.
+    fn parse_number(&mut self) -> Result<Option<f64>, String> {
+        let start = self.pos;
+        while self
+            .chars
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        let value: f64 = text.parse().map_err(|_| {
+            format!(
+                "Invalid number '{}' in chemical formula '{}'",
+                text, self.formula
+            )
+        })?;
+        if value <= 0.0 {
+            return Err(format!(
+                "Amounts in chemical formula '{}' must be positive",
+                self.formula
+            ));
+        }
+        Ok(Some(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(amounts: &[(String, f64)], symbol: &str) -> f64 {
+        amounts
+            .iter()
+            .find(|(s, _)| s == symbol)
+            .map(|(_, a)| *a)
+            .unwrap_or(0.0)
+    }
+
+    #[test]
+    fn test_simple_formula() {
+        let amounts = parse_formula("Li4SiO4").unwrap();
+        assert_eq!(
+            amounts,
+            vec![
+                ("Li".to_string(), 4.0),
+                ("Si".to_string(), 1.0),
+                ("O".to_string(), 4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fractional_subscripts() {
+        let amounts = parse_formula("Pb84.2Li15.8").unwrap();
+        assert!((amount(&amounts, "Pb") - 84.2).abs() < 1e-12);
+        assert!((amount(&amounts, "Li") - 15.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parentheses_and_repeated_elements() {
+        let amounts = parse_formula("Ca3(PO4)2").unwrap();
+        assert_eq!(amount(&amounts, "Ca"), 3.0);
+        assert_eq!(amount(&amounts, "P"), 2.0);
+        assert_eq!(amount(&amounts, "O"), 8.0);
+
+        let amounts = parse_formula("CH3COOH").unwrap();
+        assert_eq!(amounts.len(), 3);
+        assert_eq!(amount(&amounts, "C"), 2.0);
+        assert_eq!(amount(&amounts, "H"), 4.0);
+        assert_eq!(amount(&amounts, "O"), 2.0);
+
+        let amounts = parse_formula("K4[Fe(CN)6]").unwrap();
+        assert_eq!(amount(&amounts, "C"), 6.0);
+        assert_eq!(amount(&amounts, "N"), 6.0);
+        assert_eq!(amount(&amounts, "Fe"), 1.0);
+    }
+
+    #[test]
+    fn test_hydrates() {
+        for formula in ["CaSO4·2H2O", "CaSO4*2H2O", "CaSO4 . 2H2O", "CaSO4•2(H2O)"] {
+            let amounts = parse_formula(formula).unwrap();
+            assert_eq!(amount(&amounts, "Ca"), 1.0, "{}", formula);
+            assert_eq!(amount(&amounts, "S"), 1.0, "{}", formula);
+            assert_eq!(amount(&amounts, "O"), 6.0, "{}", formula);
+            assert_eq!(amount(&amounts, "H"), 4.0, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_invalid_formulas() {
+        assert!(parse_formula("").is_err());
+        assert!(parse_formula("Xx2O").is_err());
+        assert!(parse_formula("li2O").is_err());
+        assert!(parse_formula("Ca3(PO4").is_err());
+        assert!(parse_formula("CaPO4)2").is_err());
+        assert!(parse_formula("H2O·").is_err());
+        assert!(parse_formula("H0").is_err());
+        assert!(parse_formula("Li2O$").is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_hydrate_dot() {
+        let error = parse_formula("CaSO4.2H2O").unwrap_err();
+        assert!(error.contains("'4.2H'") && error.contains("'·' or '*'"), "{}", error);
+        assert!(parse_formula("Na2CO3.10H2O").is_err());
+        assert!(parse_formula("Li2.5(Pb)").is_err());
+
+        // Other fractional subscripts make the '.' a decimal point
+        let amounts = parse_formula("Li2.5Pb1.0").unwrap();
+        assert_eq!(amount(&amounts, "Li"), 2.5);
+        assert_eq!(amount(&amounts, "Pb"), 1.0);
+    }
+}
@@ -0,0 +1,236 @@
+// Provides the ENDF interpolation laws used between tabulated cross section points
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// ENDF interpolation law between two tabulated points, identified in the
+/// data by its ENDF code (the `INT` value).
+///
+/// * `Histogram` (1): y is constant, equal to the value at the lower point.
+/// * `LinLin` (2): y is linear in x.
+/// * `LinLog` (3): y is linear in ln(x).
+/// * `LogLin` (4): ln(y) is linear in x.
+/// * `LogLog` (5): ln(y) is linear in ln(x).
+///
+/// Logarithmic laws fall back to lin-lin on intervals with non-positive values,
+/// where the logarithm is undefined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum InterpolationLaw {
+    Histogram,
+    #[default]
+    LinLin,
+    LinLog,
+    LogLin,
+    LogLog,
+}
+
+impl InterpolationLaw {
+    /// Law for an ENDF interpolation code (1 to 5).
+    pub fn from_endf(code: i32) -> Result<Self, String> {
+        match code {
+            1 => Ok(InterpolationLaw::Histogram),
+            2 => Ok(InterpolationLaw::LinLin),
+            3 => Ok(InterpolationLaw::LinLog),
+            4 => Ok(InterpolationLaw::LogLin),
+            5 => Ok(InterpolationLaw::LogLog),
+            _ => Err(format!(
+                "Unknown interpolation law {}, expected an ENDF code from 1 (histogram) to 5 (log-log)",
+                code
+            )),
+        }
+    }
+
+    /// ENDF interpolation code of this law.
+    pub fn endf_code(&self) -> i32 {
+        match self {
+            InterpolationLaw::Histogram => 1,
+            InterpolationLaw::LinLin => 2,
+            InterpolationLaw::LinLog => 3,
+            InterpolationLaw::LogLin => 4,
+            InterpolationLaw::LogLog => 5,
+        }
+    }
+
+    /// Value at `x` between the points (`x1`, `y1`) and (`x2`, `y2`).
+    pub fn interpolate(&self, x1: f64, x2: f64, y1: f64, y2: f64, x: f64) -> f64 {
+        if x2 == x1 {
+            return y1;
+        }
+        let log_x = x1 > 0.0 && x > 0.0;
+        let log_y = y1 > 0.0 && y2 > 0.0;
+        match self {
+            InterpolationLaw::Histogram => y1,
+            InterpolationLaw::LinLog if log_x => {
+                y1 + (y2 - y1) * (x / x1).ln() / (x2 / x1).ln()
+            }
+            InterpolationLaw::LogLin if log_y => {
+                y1 * ((y2 / y1).ln() * (x - x1) / (x2 - x1)).exp()
+            }
+            InterpolationLaw::LogLog if log_x && log_y => {
+                y1 * ((y2 / y1).ln() * (x / x1).ln() / (x2 / x1).ln()).exp()
+            }
+            _ => y1 + (x - x1) * (y2 - y1) / (x2 - x1),
+        }
+    }
+}
+
+impl fmt::Display for InterpolationLaw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            InterpolationLaw::Histogram => "histogram",
+            InterpolationLaw::LinLin => "lin-lin",
+            InterpolationLaw::LinLog => "lin-log",
+            InterpolationLaw::LogLin => "log-lin",
+            InterpolationLaw::LogLog => "log-log",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Check ENDF interpolation regions for a table of `points` values.
+///
+/// `breakpoints` are the ENDF `NBT` values: the 1-based index of the last
+/// point of each region, increasing and ending at `points`. `laws` holds the
+/// ENDF code of each region. With no breakpoints a single law (or none,
+/// meaning lin-lin) applies to the whole table.
+pub fn check_interpolation_regions(
+    breakpoints: &[usize],
+    laws: &[i32],
+    points: usize,
+) -> Result<(), String> {
+    for &law in laws {
+        InterpolationLaw::from_endf(law)?;
+    }
+    if breakpoints.is_empty() {
+        return match laws.len() {
+            0 | 1 => Ok(()),
+            n => Err(format!(
+                "{} interpolation laws given without breakpoints, expected one law per region",
+                n
+            )),
+        };
+    }
+    if breakpoints.len() != laws.len() {
+        return Err(format!(
+            "{} interpolation breakpoints but {} interpolation laws, expected one law per region",
+            breakpoints.len(),
+            laws.len()
+        ));
+    }
+    if breakpoints.windows(2).any(|w| w[1] <= w[0]) || breakpoints[0] < 2 {
+        return Err(format!(
+            "Interpolation breakpoints must be increasing point numbers above 1, got {:?}",
+            breakpoints
+        ));
+    }
+    if breakpoints[breakpoints.len() - 1] != points {
+        return Err(format!(
+            "Last interpolation breakpoint is {} but the table has {} points",
+            breakpoints[breakpoints.len() - 1],
+            points
+        ));
+    }
+    Ok(())
+}
+
+/// Interpolation law of the interval between points `interval` and
+/// `interval + 1` (0-based) of a table with the given regions.
+///
+/// Intervals past the last breakpoint use the last law. Unknown codes and
+/// missing laws are treated as lin-lin.
+pub fn interval_law(breakpoints: &[usize], laws: &[i32], interval: usize) -> InterpolationLaw {
+    let region = breakpoints
+        .partition_point(|&last| last < interval + 2)
+        .min(laws.len().saturating_sub(1));
+    laws.get(region)
+        .and_then(|&code| InterpolationLaw::from_endf(code).ok())
+        .unwrap_or_default()
+}
+
+/// Interpolate tabulated `y(x)` at `x_new` following ENDF interpolation regions
+/// (see [`check_interpolation_regions`]).
+///
+/// If `x_new` is outside the range of `x`, returns the first or last y value,
+/// as [`crate::utilities::interpolate_linear`] does.
+pub fn interpolate_tabulated(
+    x: &[f64],
+    y: &[f64],
+    breakpoints: &[usize],
+    laws: &[i32],
+    x_new: f64,
+) -> f64 {
+    if x.is_empty() || y.is_empty() {
+        return f64::NAN;
+    }
+    let last = x.len().min(y.len()) - 1;
+    if last == 0 || x_new <= x[0] {
+        return y[0];
+    }
+    if x_new >= x[last] {
+        return y[last];
+    }
+    // Largest idx with x[idx] <= x_new
+    let idx = x[..=last].partition_point(|&xi| xi <= x_new) - 1;
+    if x[idx] == x_new {
+        return y[idx];
+    }
+    interval_law(breakpoints, laws, idx).interpolate(x[idx], x[idx + 1], y[idx], y[idx + 1], x_new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_laws_between_two_points() {
+        let (x1, x2, y1, y2) = (1.0, 100.0, 2.0, 200.0);
+        assert_eq!(InterpolationLaw::Histogram.interpolate(x1, x2, y1, y2, 10.0), 2.0);
+        assert!((InterpolationLaw::LinLin.interpolate(x1, x2, y1, y2, 10.0) - 20.0).abs() < 1e-12);
+        // y linear in ln(x): halfway in ln(x) is halfway in y
+        assert!((InterpolationLaw::LinLog.interpolate(x1, x2, y1, y2, 10.0) - 101.0).abs() < 1e-12);
+        // ln(y) linear in x
+        let log_lin = InterpolationLaw::LogLin.interpolate(x1, x2, y1, y2, 50.5);
+        assert!((log_lin - 20.0).abs() < 1e-10);
+        // y = 2 x is a straight line on log-log axes
+        assert!((InterpolationLaw::LogLog.interpolate(x1, x2, y1, y2, 37.0) - 74.0).abs() < 1e-10);
+        // Log laws need positive values
+        assert_eq!(InterpolationLaw::LogLog.interpolate(1.0, 3.0, 0.0, 2.0, 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_endf_codes() {
+        for code in 1..=5 {
+            assert_eq!(InterpolationLaw::from_endf(code).unwrap().endf_code(), code);
+        }
+        assert!(InterpolationLaw::from_endf(6).is_err());
+        assert_eq!(InterpolationLaw::default(), InterpolationLaw::LinLin);
+        assert_eq!(InterpolationLaw::LogLog.to_string(), "log-log");
+    }
+
+    #[test]
+    fn test_interpolate_tabulated_with_regions() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let y = [1.0, 2.0, 4.0, 8.0];
+        // Points 1-2 histogram, points 2-4 lin-lin
+        let (breakpoints, laws) = ([2, 4], [1, 2]);
+        assert_eq!(interpolate_tabulated(&x, &y, &breakpoints, &laws, 1.5), 1.0);
+        assert_eq!(interpolate_tabulated(&x, &y, &breakpoints, &laws, 2.5), 3.0);
+        assert_eq!(interpolate_tabulated(&x, &y, &breakpoints, &laws, 3.0), 4.0);
+        assert_eq!(interpolate_tabulated(&x, &y, &breakpoints, &laws, 0.5), 1.0);
+        assert_eq!(interpolate_tabulated(&x, &y, &breakpoints, &laws, 9.0), 8.0);
+        // A single law without breakpoints, and lin-lin by default
+        assert_eq!(interpolate_tabulated(&x, &y, &[], &[1], 3.5), 4.0);
+        assert_eq!(interpolate_tabulated(&x, &y, &[], &[], 3.5), 6.0);
+    }
+
+    #[test]
+    fn test_check_interpolation_regions() {
+        assert!(check_interpolation_regions(&[], &[], 4).is_ok());
+        assert!(check_interpolation_regions(&[], &[5], 4).is_ok());
+        assert!(check_interpolation_regions(&[2, 4], &[1, 2], 4).is_ok());
+        assert!(check_interpolation_regions(&[], &[1, 2], 4).is_err());
+        assert!(check_interpolation_regions(&[2, 4], &[1], 4).is_err());
+        assert!(check_interpolation_regions(&[3, 2], &[1, 2], 4).is_err());
+        assert!(check_interpolation_regions(&[2, 3], &[1, 2], 4).is_err());
+        assert!(check_interpolation_regions(&[4], &[7], 4).is_err());
+    }
+}
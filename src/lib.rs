@@ -1,20 +1,57 @@
 // First, import any modules and re-export the types for Rust usage
+mod ace;
 mod config;
+mod doppler;
 mod element;
+mod fispact;
+mod formula;
+mod interpolation;
 mod material;
+mod material_library;
 mod materials;
+mod mcnp;
 mod nuclide;
+mod nuclide_binary;
+mod nuclide_id;
+mod nuclide_names;
+mod openmc;
 mod reaction;
+mod serpent;
+mod temperature;
 mod utilities;
+mod validation;
 mod url_cache;
 
+pub use ace::{read_nuclide_from_ace, read_nuclide_from_ace_bytes};
 pub use config::Config;
 pub use element::Element;
-pub use material::Material;
+pub use formula::parse_formula;
+pub use interpolation::{interpolate_tabulated, InterpolationLaw};
+pub use material::{FractionType, Impurity, Material, MixType};
+pub use material_library::{
+    get_library_material, material_library_names, search_material_library, LibraryMaterial,
+    MATERIAL_LIBRARY,
+};
+pub use fispact::{material_to_fispact_fuel, material_to_fispact_mass};
 pub use materials::Materials;
+pub use mcnp::{
+    material_to_mcnp, materials_from_mcnp, materials_to_mcnp, mcnp_cell_density, nuclide_to_zaid,
+    zaid_to_nuclide,
+};
 pub use reaction::Reaction;
+pub use serpent::{material_to_serpent, materials_to_serpent};
+pub use temperature::TemperatureMethod;
 pub use nuclide::Nuclide;
+pub use nuclide_binary::{
+    convert_json_to_binary, read_nuclide_from_binary, NuclideBinary, BINARY_MAGIC, BINARY_VERSION,
+};
+pub use nuclide_id::NuclideId;
+pub use nuclide_names::{parse_nuclide_name, to_gnds_name, to_hyphenated_name, to_zaid};
+pub use openmc::{
+    material_to_openmc_xml_element, materials_from_openmc_xml, materials_to_openmc_xml,
+};
 pub use utilities::{interpolate_linear, interpolate_log_log};
+pub use validation::{ValidationIssue, ValidationIssueKind, ValidationReport, DEFAULT_SUM_TOLERANCE};
 
 // Import PyO3 items conditionally
 #[cfg(feature = "pyo3")]
@@ -37,6 +74,8 @@ mod materials_python;
 mod nuclide_python;
 #[cfg(feature = "pyo3")]
 mod reaction_python;
+#[cfg(feature = "pyo3")]
+mod validation_python;
 
 // Re-export Python modules for Maturin to find
 #[cfg(feature = "pyo3")]
@@ -51,6 +90,8 @@ pub use materials_python::*;
 pub use nuclide_python::*;
 #[cfg(feature = "pyo3")]
 pub use reaction_python::*;
+#[cfg(feature = "pyo3")]
+pub use validation_python::*;
 
 // Declare the data module
 pub mod data;
@@ -109,14 +150,29 @@ fn materials_for_mc(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_class::<reaction_python::PyReaction>()?; // Exposed as Reaction in Python
     m.add_class::<config_python::PyConfig>()?;
     m.add_class::<element_python::PyElement>()?;
+    m.add_class::<validation_python::PyValidationReport>()?;
     m.add_function(wrap_pyfunction!(
         nuclide_python::py_read_nuclide_from_json,
         m
     )?)?;
+    m.add_function(wrap_pyfunction!(
+        nuclide_python::py_read_nuclide_from_binary,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        nuclide_python::py_convert_json_to_binary,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(nuclide_python::py_read_nuclide_from_ace, m)?)?;
     m.add_function(wrap_pyfunction!(nuclide_python::clear_nuclide_cache, m)?)?;
     m.add_function(wrap_pyfunction!(crate::data_python::natural_abundance, m)?)?;
     m.add_function(wrap_pyfunction!(crate::data_python::element_nuclides, m)?)?;
     m.add_function(wrap_pyfunction!(crate::data_python::element_names, m)?)?;
     m.add_function(wrap_pyfunction!(crate::data_python::atomic_masses, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::data_python::py_to_gnds_name, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::data_python::py_to_zaid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::data_python::py_to_hyphenated_name, m)?)?;
+    m.add_function(wrap_pyfunction!(material_python::material_library_names, m)?)?;
+    m.add_function(wrap_pyfunction!(material_python::search_material_library, m)?)?;
     Ok(())
 }
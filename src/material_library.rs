@@ -0,0 +1,416 @@
+// Provides a compendium of predefined reference materials
+use crate::material::{FractionType, Material};
+
+/// A predefined material with a reference composition and density.
+///
+/// Each composition entry is a chemical formula (a single element symbol is
+/// the simplest formula) and its fraction, interpreted according to
+/// `fraction_type`. Elements are expanded to natural isotopes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryMaterial {
+    /// Name used to look up the material (e.g. "Eurofer97").
+    pub name: &'static str,
+    /// Short description including the source of the composition.
+    pub description: &'static str,
+    /// Density in g/cm³.
+    pub density: f64,
+    /// Whether the composition fractions are atom or weight fractions.
+    pub fraction_type: FractionType,
+    /// Composition as (formula, fraction) pairs.
+    pub composition: &'static [(&'static str, f64)],
+}
+
+impl LibraryMaterial {
+    /// Build a [`Material`] from this entry, named after the entry.
+    pub fn to_material(&self) -> Result<Material, String> {
+        let mut material = Material::new();
+        material.set_name(self.name);
+        for (formula, fraction) in self.composition {
+            material.add_elements_from_formula_with_fraction_type(
+                formula,
+                *fraction,
+                self.fraction_type,
+            )?;
+        }
+        material.set_density("g/cm3", self.density)?;
+        Ok(material)
+    }
+}
+
+/// The built-in material compendium.
+pub static MATERIAL_LIBRARY: &[LibraryMaterial] = &[
+    // Structural materials
+    LibraryMaterial {
+        name: "Eurofer97",
+        description: "Reduced activation ferritic-martensitic steel (EUROFER97 nominal specification)",
+        density: 7.798,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("Fe", 89.04),
+            ("Cr", 9.0),
+            ("W", 1.1),
+            ("Mn", 0.4),
+            ("V", 0.2),
+            ("Ta", 0.12),
+            ("C", 0.11),
+            ("N", 0.03),
+        ],
+    },
+    LibraryMaterial {
+        name: "SS316L",
+        description: "Austenitic stainless steel 316L (ASTM A240 maximum alloying, Fe balance)",
+        density: 8.0,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("Fe", 65.545),
+            ("Cr", 17.0),
+            ("Ni", 12.0),
+            ("Mo", 2.5),
+            ("Mn", 2.0),
+            ("Si", 0.75),
+            ("N", 0.1),
+            ("P", 0.045),
+            ("C", 0.03),
+            ("S", 0.03),
+        ],
+    },
+    LibraryMaterial {
+        name: "SS304",
+        description: "Austenitic stainless steel 304 (ASTM A240 nominal, Fe balance)",
+        density: 8.0,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("Fe", 68.745),
+            ("Cr", 19.0),
+            ("Ni", 9.25),
+            ("Mn", 2.0),
+            ("Si", 0.75),
+            ("N", 0.1),
+            ("C", 0.08),
+            ("P", 0.045),
+            ("S", 0.03),
+        ],
+    },
+    LibraryMaterial {
+        name: "Inconel718",
+        description: "Nickel based superalloy Inconel 718 (nominal, Fe balance)",
+        density: 8.19,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("Ni", 52.5),
+            ("Cr", 19.0),
+            ("Fe", 18.88),
+            ("Nb", 5.13),
+            ("Mo", 3.05),
+            ("Ti", 0.9),
+            ("Al", 0.5),
+            ("C", 0.04),
+        ],
+    },
+    LibraryMaterial {
+        name: "Zircaloy4",
+        description: "Zircaloy-4 fuel cladding (ASTM B350 nominal, Zr balance)",
+        density: 6.56,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("Zr", 98.12),
+            ("Sn", 1.45),
+            ("Fe", 0.21),
+            ("O", 0.12),
+            ("Cr", 0.1),
+        ],
+    },
+    LibraryMaterial {
+        name: "CuCrZr",
+        description: "Precipitation hardened copper alloy CuCrZr (ITER grade nominal, Cu balance)",
+        density: 8.9,
+        fraction_type: FractionType::Weight,
+        composition: &[("Cu", 99.2), ("Cr", 0.7), ("Zr", 0.1)],
+    },
+    LibraryMaterial {
+        name: "Copper",
+        description: "Pure copper",
+        density: 8.96,
+        fraction_type: FractionType::Atom,
+        composition: &[("Cu", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Tungsten",
+        description: "Pure tungsten",
+        density: 19.3,
+        fraction_type: FractionType::Atom,
+        composition: &[("W", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Lead",
+        description: "Pure lead",
+        density: 11.35,
+        fraction_type: FractionType::Atom,
+        composition: &[("Pb", 1.0)],
+    },
+    // Breeders and multipliers
+    LibraryMaterial {
+        name: "Beryllium",
+        description: "Pure beryllium neutron multiplier",
+        density: 1.85,
+        fraction_type: FractionType::Atom,
+        composition: &[("Be", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Lithium",
+        description: "Liquid or solid lithium metal at room temperature, natural enrichment",
+        density: 0.534,
+        fraction_type: FractionType::Atom,
+        composition: &[("Li", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Li4SiO4",
+        description: "Lithium orthosilicate ceramic breeder, theoretical density, natural enrichment",
+        density: 2.40,
+        fraction_type: FractionType::Atom,
+        composition: &[("Li4SiO4", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Li2TiO3",
+        description: "Lithium metatitanate ceramic breeder, theoretical density, natural enrichment",
+        density: 3.43,
+        fraction_type: FractionType::Atom,
+        composition: &[("Li2TiO3", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Li2O",
+        description: "Lithium oxide ceramic breeder, theoretical density, natural enrichment",
+        density: 2.013,
+        fraction_type: FractionType::Atom,
+        composition: &[("Li2O", 1.0)],
+    },
+    LibraryMaterial {
+        name: "PbLi",
+        description: "Lead lithium eutectic Pb-15.8Li (at.%), natural enrichment, density near 600 K",
+        density: 9.4,
+        fraction_type: FractionType::Atom,
+        composition: &[("Pb84.2Li15.8", 1.0)],
+    },
+    LibraryMaterial {
+        name: "FLiBe",
+        description: "Molten salt Li2BeF4, natural enrichment, density near 900 K",
+        density: 1.94,
+        fraction_type: FractionType::Atom,
+        composition: &[("Li2BeF4", 1.0)],
+    },
+    // Coolants and moderators
+    LibraryMaterial {
+        name: "Water",
+        description: "Light water at room temperature",
+        density: 1.0,
+        fraction_type: FractionType::Atom,
+        composition: &[("H2O", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Helium",
+        description: "Helium gas at standard temperature and pressure",
+        density: 0.0001786,
+        fraction_type: FractionType::Atom,
+        composition: &[("He", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Air",
+        description: "Dry air near sea level (PNNL-15870 Rev. 1)",
+        density: 0.001205,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("N", 0.755268),
+            ("O", 0.231781),
+            ("Ar", 0.012827),
+            ("C", 0.000124),
+        ],
+    },
+    LibraryMaterial {
+        name: "Graphite",
+        description: "Reactor grade graphite",
+        density: 1.7,
+        fraction_type: FractionType::Atom,
+        composition: &[("C", 1.0)],
+    },
+    LibraryMaterial {
+        name: "Polyethylene",
+        description: "High density polyethylene (C2H4)n",
+        density: 0.94,
+        fraction_type: FractionType::Atom,
+        composition: &[("C2H4", 1.0)],
+    },
+    // Shielding and absorbers
+    LibraryMaterial {
+        name: "ConcreteOrdinary",
+        description: "Ordinary concrete NBS 03 (PNNL-15870 Rev. 1)",
+        density: 2.35,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("H", 0.005558),
+            ("O", 0.498076),
+            ("Na", 0.017101),
+            ("Mg", 0.002565),
+            ("Al", 0.045746),
+            ("Si", 0.315092),
+            ("S", 0.001283),
+            ("K", 0.019239),
+            ("Ca", 0.082941),
+            ("Fe", 0.012398),
+        ],
+    },
+    LibraryMaterial {
+        name: "ConcreteBarite",
+        description: "Barite concrete type BA (PNNL-15870 Rev. 1)",
+        density: 3.35,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("H", 0.003585),
+            ("O", 0.311622),
+            ("Mg", 0.001195),
+            ("Al", 0.004183),
+            ("Si", 0.010457),
+            ("S", 0.107858),
+            ("Ca", 0.050194),
+            ("Fe", 0.047505),
+            ("Ba", 0.4634),
+        ],
+    },
+    LibraryMaterial {
+        name: "ConcreteMagnetite",
+        description: "Magnetite concrete (PNNL-15870 Rev. 1)",
+        density: 3.53,
+        fraction_type: FractionType::Weight,
+        composition: &[
+            ("H", 0.003319),
+            ("O", 0.33849),
+            ("Mg", 0.009405),
+            ("Al", 0.024895),
+            ("Si", 0.02529),
+            ("S", 0.000553),
+            ("Ca", 0.080892),
+            ("Ti", 0.056191),
+            ("V", 0.002628),
+            ("Cr", 0.001328),
+            ("Mn", 0.001549),
+            ("Fe", 0.45546),
+        ],
+    },
+    LibraryMaterial {
+        name: "B4C",
+        description: "Boron carbide, theoretical density, natural boron",
+        density: 2.52,
+        fraction_type: FractionType::Atom,
+        composition: &[("B4C", 1.0)],
+    },
+    LibraryMaterial {
+        name: "SiC",
+        description: "Silicon carbide, theoretical density",
+        density: 3.21,
+        fraction_type: FractionType::Atom,
+        composition: &[("SiC", 1.0)],
+    },
+    // Fuels
+    LibraryMaterial {
+        name: "UO2",
+        description: "Uranium dioxide fuel, theoretical density, natural uranium",
+        density: 10.97,
+        fraction_type: FractionType::Atom,
+        composition: &[("UO2", 1.0)],
+    },
+];
+
+/// Normalise a name for lookups: lowercase and without separators.
+fn normalise(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Find a compendium entry by name.
+///
+/// Matching ignores case and separators, so "eurofer-97" finds "Eurofer97".
+pub fn get_library_material(name: &str) -> Option<&'static LibraryMaterial> {
+    let key = normalise(name);
+    MATERIAL_LIBRARY.iter().find(|entry| normalise(entry.name) == key)
+}
+
+/// Names of all materials in the compendium.
+pub fn material_library_names() -> Vec<&'static str> {
+    MATERIAL_LIBRARY.iter().map(|entry| entry.name).collect()
+}
+
+/// Search the compendium by name or description (case-insensitive substring match).
+pub fn search_material_library(query: &str) -> Vec<&'static LibraryMaterial> {
+    let query_lower = query.to_lowercase();
+    let query_normalised = normalise(query);
+    MATERIAL_LIBRARY
+        .iter()
+        .filter(|entry| {
+            normalise(entry.name).contains(&query_normalised)
+                || entry.description.to_lowercase().contains(&query_lower)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_entries_build() {
+        for entry in MATERIAL_LIBRARY {
+            let material = entry
+                .to_material()
+                .unwrap_or_else(|e| panic!("{} failed to build: {}", entry.name, e));
+            assert_eq!(material.get_name(), Some(entry.name));
+            assert!(!material.nuclides.is_empty(), "{}", entry.name);
+            let mass_density = material.get_mass_density().unwrap();
+            assert!((mass_density - entry.density).abs() < 1e-12, "{}", entry.name);
+        }
+    }
+
+    #[test]
+    fn test_weight_fractions_sum_to_one() {
+        for entry in MATERIAL_LIBRARY {
+            if entry.fraction_type == FractionType::Weight {
+                let total: f64 = entry.composition.iter().map(|(_, f)| f).sum();
+                let expected = if total > 2.0 { 100.0 } else { 1.0 };
+                assert!((total - expected).abs() < 1e-3 * expected, "{}: {}", entry.name, total);
+            }
+        }
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names: Vec<String> = MATERIAL_LIBRARY.iter().map(|e| normalise(e.name)).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), MATERIAL_LIBRARY.len());
+    }
+
+    #[test]
+    fn test_lookup_ignores_case_and_separators() {
+        assert_eq!(get_library_material("Eurofer97").unwrap().name, "Eurofer97");
+        assert_eq!(get_library_material("eurofer-97").unwrap().name, "Eurofer97");
+        assert_eq!(get_library_material("ss 316L").unwrap().name, "SS316L");
+        assert!(get_library_material("unobtainium").is_none());
+    }
+
+    #[test]
+    fn test_search() {
+        let concretes: Vec<&str> = search_material_library("concrete")
+            .iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(
+            concretes,
+            vec!["ConcreteOrdinary", "ConcreteBarite", "ConcreteMagnetite"]
+        );
+        let steels = search_material_library("steel");
+        assert!(steels.iter().any(|e| e.name == "Eurofer97"));
+        assert!(steels.iter().any(|e| e.name == "SS316L"));
+        assert!(search_material_library("unobtainium").is_empty());
+        assert_eq!(material_library_names().len(), MATERIAL_LIBRARY.len());
+    }
+}
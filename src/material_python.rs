@@ -1,5 +1,6 @@
 use pyo3::prelude::*;
-use crate::material::Material;
+use crate::material::{FractionType, Impurity, Material, MixType};
+use crate::temperature::TemperatureMethod;
 use pyo3::exceptions::PyValueError;
 use std::collections::HashMap;
 
@@ -58,34 +59,517 @@ impl PyMaterial {
         PyMaterial { internal }
     }
 
-    /// Add (or update) a nuclide number fraction.
+    /// Build a homogenised material from constituent materials.
+    ///
+    /// Args:
+    ///     materials (List[Tuple[Material, float]]): Constituent materials with their fractions.
+    ///     mix_type (str): "vo" for volume fractions (default), "wo" for weight
+    ///         fractions or "ao" for atom fractions. Volume fractions summing to
+    ///         less than one leave the remainder as void.
+    ///
+    /// Returns:
+    ///     Material: Mixture with the combined composition and density in g/cm3.
+    ///
+    /// Raises:
+    ///     ValueError: If a constituent has no composition or mass density, or
+    ///         the fractions are invalid.
+    #[staticmethod]
+    #[pyo3(signature = (materials, mix_type="vo"))]
+    fn mix(materials: Vec<(PyRef<PyMaterial>, f64)>, mix_type: &str) -> PyResult<PyMaterial> {
+        let mix_type: MixType = mix_type.parse().map_err(PyValueError::new_err)?;
+        let constituents: Vec<(Material, f64)> = materials
+            .iter()
+            .map(|(m, f)| (m.internal.clone(), *f))
+            .collect();
+        let internal = Material::mix(&constituents, mix_type).map_err(PyValueError::new_err)?;
+        Ok(PyMaterial { internal })
+    }
+
+    /// Create a material from the built-in compendium.
+    ///
+    /// Args:
+    ///     name (str): Compendium material name (e.g. "Eurofer97", "PbLi"); case and
+    ///         separators are ignored.
+    ///
+    /// Returns:
+    ///     Material: Material with the reference composition and density.
+    ///
+    /// Raises:
+    ///     ValueError: If the name is not in the compendium.
+    #[staticmethod]
+    fn from_library(name: &str) -> PyResult<PyMaterial> {
+        let internal = Material::from_library(name).map_err(PyValueError::new_err)?;
+        Ok(PyMaterial { internal })
+    }
+
+    /// Serialize the material definition to a JSON string.
+    ///
+    /// Includes name, composition (fraction type and data source per nuclide),
+    /// density and units, temperature and volume, but no cross section data.
+    ///
+    /// Returns:
+    ///     str: JSON representation of the material.
+    fn to_json(&self) -> PyResult<String> {
+        self.internal
+            .to_json()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a material from a JSON string written by to_json.
+    ///
+    /// Args:
+    ///     json (str): JSON representation of a material.
+    ///
+    /// Returns:
+    ///     Material: The restored material.
+    ///
+    /// Raises:
+    ///     ValueError: If the JSON is not a valid material definition.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<PyMaterial> {
+        let internal = Material::from_json(json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyMaterial { internal })
+    }
+
+    /// Write the material as an OpenMC materials.xml document (material ID 1).
+    ///
+    /// Returns:
+    ///     str: XML text with nuclide names, fractions, density, temperature and volume.
+    ///
+    /// Raises:
+    ///     ValueError: If the material has no nuclides or density.
+    fn to_openmc_xml(&self) -> PyResult<String> {
+        self.internal.to_openmc_xml().map_err(PyValueError::new_err)
+    }
+
+    /// Write the material to an OpenMC materials.xml file.
+    ///
+    /// Args:
+    ///     path (str): Output file path (e.g. "materials.xml").
+    #[pyo3(signature = (path="materials.xml"))]
+    fn export_to_openmc_xml(&self, path: &str) -> PyResult<()> {
+        self.internal
+            .export_to_openmc_xml(path)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write the material as an MCNP m card.
+    ///
+    /// Weight fraction compositions are written as negative fractions, all
+    /// others as positive atom fractions.
+    ///
+    /// Args:
+    ///     number (int): MCNP material number (default 1).
+    ///     library (str, optional): ZAID library suffix (e.g. "80c").
+    ///
+    /// Returns:
+    ///     str: The m card, preceded by comments with the name and cell density.
+    ///
+    /// Raises:
+    ///     ValueError: If the material has no nuclides or a nuclide has no ZAID.
+    #[pyo3(signature = (number=1, library=None))]
+    fn to_mcnp(&self, number: u32, library: Option<&str>) -> PyResult<String> {
+        self.internal
+            .to_mcnp(number, library)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Write the material as a Serpent mat block.
+    ///
+    /// Args:
+    ///     library (str, optional): ZAID library suffix (e.g. "03c").
+    ///
+    /// Returns:
+    ///     str: The mat block, named after the material (or "m1" if unnamed).
+    ///
+    /// Raises:
+    ///     ValueError: If the material has no nuclides or density.
+    #[pyo3(signature = (library=None))]
+    fn to_serpent(&self, library: Option<&str>) -> PyResult<String> {
+        self.internal.to_serpent(library).map_err(PyValueError::new_err)
+    }
+
+    /// Write the FISPACT-II DENSITY and MASS input (element weight percentages).
+    ///
+    /// The mass in kg is calculated from the volume (cm3) and density.
+    ///
+    /// Returns:
+    ///     str: The DENSITY and MASS lines.
+    ///
+    /// Raises:
+    ///     ValueError: If the volume or density is not set.
+    fn to_fispact_mass(&self) -> PyResult<String> {
+        self.internal.to_fispact_mass().map_err(PyValueError::new_err)
+    }
+
+    /// Write the FISPACT-II DENSITY and FUEL input (number of atoms of each nuclide).
+    ///
+    /// Returns:
+    ///     str: The DENSITY and FUEL lines.
+    ///
+    /// Raises:
+    ///     ValueError: If the volume or density is not set.
+    fn to_fispact_fuel(&self) -> PyResult<String> {
+        self.internal.to_fispact_fuel().map_err(PyValueError::new_err)
+    }
+
+    /// MCNP cell density of the material.
+    ///
+    /// Returns:
+    ///     float: Positive atom density in atom/b-cm or negative mass density in g/cm3.
+    ///
+    /// Raises:
+    ///     ValueError: If the material has no density.
+    fn get_mcnp_cell_density(&self) -> PyResult<f64> {
+        self.internal
+            .get_mcnp_cell_density()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Add (or update) a nuclide fraction.
     ///
     /// Args:
     ///     nuclide (str): Nuclide name (e.g. "Fe56").
-    ///     fraction (float): Number fraction (will be normalized with others later).
+    ///     fraction (float): Fraction (will be normalized with others later).
+    ///     fraction_type (str): "ao" for atom fraction (default) or "wo" for weight fraction.
     ///
     /// Raises:
-    ///     ValueError: On invalid fraction or name.
-    fn add_nuclide(&mut self, nuclide: String, fraction: f64) -> PyResult<()> {
+    ///     ValueError: On invalid fraction, fraction type or name.
+    #[pyo3(signature = (nuclide, fraction, fraction_type="ao"))]
+    fn add_nuclide(&mut self, nuclide: String, fraction: f64, fraction_type: &str) -> PyResult<()> {
+        let fraction_type: FractionType = fraction_type.parse().map_err(PyValueError::new_err)?;
         self.internal
-            .add_nuclide(&nuclide, fraction)
+            .add_nuclide_with_fraction_type(&nuclide, fraction, fraction_type)
             .map_err(|e| PyValueError::new_err(e))
     }
 
     /// Set material density.
     ///
     /// Args:
-    ///     unit (str): Density unit (e.g. "g/cm3", "atoms/b-cm").
-    ///     value (float): Density value.
+    ///     unit (str): Density unit, one of "g/cm3", "g/cc", "kg/m3", "atom/b-cm",
+    ///         "atom/cm3" or "sum". With "sum" the nuclide fractions are absolute
+    ///         densities and no value is needed.
+    ///     value (Optional[float]): Density value, required unless unit is "sum".
     ///
     /// Raises:
-    ///     ValueError: If unit not supported.
-    fn set_density(&mut self, unit: String, value: f64) -> PyResult<()> {
+    ///     ValueError: If unit not supported or value missing / not positive.
+    #[pyo3(signature = (unit, value=None))]
+    fn set_density(&mut self, unit: String, value: Option<f64>) -> PyResult<()> {
+        let value = match (unit.as_str(), value) {
+            (_, Some(v)) => v,
+            ("sum", None) => 0.0,
+            (_, None) => {
+                return Err(PyValueError::new_err(format!(
+                    "A density value is required for units '{}'",
+                    unit
+                )))
+            }
+        };
         self.internal
             .set_density(&unit, value)
             .map_err(|e| PyValueError::new_err(e))
     }
 
+    /// Mass density in g/cm3, converted from whichever units the density was set in.
+    ///
+    /// Returns:
+    ///     float: Mass density in g/cm3.
+    ///
+    /// Raises:
+    ///     ValueError: If the density or composition is not defined.
+    fn get_mass_density(&self) -> PyResult<f64> {
+        self.internal
+            .get_mass_density()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Element symbols present in the composition, sorted alphabetically.
+    ///
+    /// Returns:
+    ///     List[str]: Element symbols of nuclides added directly or expanded from elements.
+    fn get_elements(&self) -> Vec<String> {
+        self.internal.get_elements()
+    }
+
+    /// Fraction of each element as it was added (in its atom or weight fraction units).
+    ///
+    /// Returns:
+    ///     Dict[str, float]: element symbol -> sum of its nuclide fractions
+    fn get_element_fractions(&self) -> HashMap<String, f64> {
+        self.internal.get_element_fractions()
+    }
+
+    /// Element a nuclide was expanded from.
+    ///
+    /// Args:
+    ///     nuclide (str): Nuclide name (e.g. "Fe56").
+    ///
+    /// Returns:
+    ///     Optional[str]: Element symbol, or None if the nuclide was added directly.
+    fn element_of(&self, nuclide: &str) -> Option<String> {
+        self.internal.element_of(nuclide).map(str::to_string)
+    }
+
+    /// Nuclides added directly with add_nuclide, sorted alphabetically.
+    fn get_direct_nuclides(&self) -> Vec<String> {
+        self.internal.get_direct_nuclides()
+    }
+
+    /// Nuclides expanded from elements by add_element, sorted alphabetically.
+    fn get_expanded_nuclides(&self) -> Vec<String> {
+        self.internal.get_expanded_nuclides()
+    }
+
+    /// Remove every isotope of an element from the composition.
+    ///
+    /// Args:
+    ///     element (str): Element symbol or name (e.g. "Cr" or "chromium").
+    ///
+    /// Raises:
+    ///     ValueError: If the element is not in the composition.
+    fn remove_element(&mut self, element: &str) -> PyResult<()> {
+        self.internal
+            .remove_element(element)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Replace an element with the natural isotopes of another, keeping its fraction.
+    ///
+    /// Args:
+    ///     element (str): Element to remove (e.g. "Pb").
+    ///     replacement (str): Element to add in its place (e.g. "Bi").
+    ///
+    /// Raises:
+    ///     ValueError: If the element is not in the composition or the replacement is unknown.
+    fn replace_element(&mut self, element: &str, replacement: &str) -> PyResult<()> {
+        self.internal
+            .replace_element(element, replacement)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Remove a nuclide from the composition.
+    ///
+    /// Args:
+    ///     nuclide (str): Nuclide name (e.g. "Li6").
+    ///
+    /// Raises:
+    ///     ValueError: If the nuclide is not in the composition.
+    fn remove_nuclide(&mut self, nuclide: &str) -> PyResult<()> {
+        self.internal
+            .remove_nuclide(nuclide)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Replace a nuclide with another, keeping the number of atoms.
+    ///
+    /// Weight fractions are scaled by the ratio of the atomic masses. If the
+    /// replacement is already present the fractions are summed.
+    ///
+    /// Args:
+    ///     nuclide (str): Nuclide to remove (e.g. "Li6").
+    ///     replacement (str): Nuclide to add in its place (e.g. "H3").
+    ///
+    /// Raises:
+    ///     ValueError: If the nuclide is not in the composition or the replacement
+    ///         is already present with a different fraction type.
+    fn replace_nuclide(&mut self, nuclide: &str, replacement: &str) -> PyResult<()> {
+        self.internal
+            .replace_nuclide(nuclide, replacement)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Multiply every fraction, or the fraction of one nuclide, by a factor.
+    ///
+    /// Args:
+    ///     factor (float): Scale factor.
+    ///     nuclide (Optional[str]): Nuclide to scale, all nuclides if None.
+    ///
+    /// Raises:
+    ///     ValueError: If the factor is negative (or zero for the whole
+    ///         composition) or the nuclide is not in the composition.
+    #[pyo3(signature = (factor, nuclide=None))]
+    fn scale(&mut self, factor: f64, nuclide: Option<&str>) -> PyResult<()> {
+        match nuclide {
+            Some(nuclide) => self.internal.scale_nuclide(nuclide, factor),
+            None => self.internal.scale(factor),
+        }
+        .map_err(PyValueError::new_err)
+    }
+
+    /// Scale the fractions so they sum to 1.
+    ///
+    /// Raises:
+    ///     ValueError: If the density units are "sum" or the fractions sum to zero.
+    fn normalize(&mut self) -> PyResult<()> {
+        self.internal.normalize().map_err(PyValueError::new_err)
+    }
+
+    /// Replace the composition with absolute atom densities of each nuclide.
+    ///
+    /// The total becomes the material density and loaded nuclear data is kept
+    /// for nuclides that remain, e.g. to update a material every depletion step.
+    ///
+    /// Args:
+    ///     densities (Dict[str, float]): Atom density of each nuclide.
+    ///     units (str): "atom/b-cm" (default) or "atom/cm3".
+    ///
+    /// Raises:
+    ///     ValueError: If a density is negative, the total is zero or the units
+    ///         are not supported.
+    #[pyo3(signature = (densities, units="atom/b-cm"))]
+    fn set_atom_densities(&mut self, densities: HashMap<String, f64>, units: &str) -> PyResult<()> {
+        self.internal
+            .set_atom_densities(&densities, units)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Add a trace element in parts per million of the whole material.
+    ///
+    /// The element is expanded to its natural isotopes and the rest of the
+    /// composition is scaled down so the impurity is exact. Add impurities once
+    /// the base composition is complete.
+    ///
+    /// Args:
+    ///     element (str): Element symbol or name (e.g. "Co").
+    ///     ppm (float): Amount in parts per million.
+    ///     units (str): "wppm" for weight ppm (default) or "appm" for atom ppm.
+    ///
+    /// Raises:
+    ///     ValueError: If the element is unknown or already in the base composition,
+    ///         or the amount is not between 0 and 1e6 ppm.
+    #[pyo3(signature = (element, ppm, units="wppm"))]
+    fn add_impurity(&mut self, element: &str, ppm: f64, units: &str) -> PyResult<()> {
+        let fraction_type = Impurity::fraction_type_from_units(units).map_err(PyValueError::new_err)?;
+        self.internal
+            .add_impurity(element, ppm, fraction_type)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Remove an impurity, leaving the rest of the composition unchanged.
+    ///
+    /// Args:
+    ///     element (str): Element symbol or name of the impurity.
+    ///
+    /// Raises:
+    ///     ValueError: If the element is not an impurity of the material.
+    fn remove_impurity(&mut self, element: &str) -> PyResult<()> {
+        self.internal
+            .remove_impurity(element)
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Impurities of the material as (element, ppm, units) tuples.
+    #[getter]
+    fn impurities(&self) -> Vec<(String, f64, String)> {
+        self.internal
+            .get_impurities()
+            .iter()
+            .map(|i| (i.element.clone(), i.ppm, i.units().to_string()))
+            .collect()
+    }
+
+    /// Table of the composition showing whether each nuclide was added directly
+    /// or expanded from an element.
+    ///
+    /// Returns:
+    ///     str: One line per nuclide with its fraction, fraction type and origin.
+    fn composition_report(&self) -> String {
+        self.internal.composition_report()
+    }
+
+    /// Average molar mass of the material's atoms, weighted by atom fraction.
+    ///
+    /// Returns:
+    ///     float: Average molar mass in g/mol.
+    ///
+    /// Raises:
+    ///     ValueError: If the composition is empty or an atomic mass is unknown.
+    fn get_average_molar_mass(&self) -> PyResult<f64> {
+        self.internal
+            .get_average_molar_mass()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Total mass of the material from its volume and density.
+    ///
+    /// Returns:
+    ///     float: Mass in grams.
+    ///
+    /// Raises:
+    ///     ValueError: If the volume or density is not set.
+    fn get_total_mass(&self) -> PyResult<f64> {
+        self.internal.get_total_mass().map_err(PyValueError::new_err)
+    }
+
+    /// Number of atoms of each nuclide in the material volume.
+    ///
+    /// Returns:
+    ///     Dict[str, float]: nuclide -> number of atoms
+    ///
+    /// Raises:
+    ///     ValueError: If the volume or density is not set.
+    fn get_atoms_per_nuclide(&self) -> PyResult<HashMap<String, f64>> {
+        self.internal
+            .get_atoms_per_nuclide()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Total number of atoms in the material volume.
+    ///
+    /// Returns:
+    ///     float: Number of atoms.
+    ///
+    /// Raises:
+    ///     ValueError: If the volume or density is not set.
+    fn get_total_atoms(&self) -> PyResult<f64> {
+        self.internal.get_total_atoms().map_err(PyValueError::new_err)
+    }
+
+    /// Atom fraction of each element in the material.
+    ///
+    /// Returns:
+    ///     Dict[str, float]: element symbol -> atom fraction (sums to 1)
+    fn get_element_atom_fractions(&self) -> HashMap<String, f64> {
+        self.internal.get_element_atom_fractions()
+    }
+
+    /// Mass fraction of each element in the material.
+    ///
+    /// Returns:
+    ///     Dict[str, float]: element symbol -> mass fraction (sums to 1)
+    ///
+    /// Raises:
+    ///     ValueError: If the composition is empty or an atomic mass is unknown.
+    fn get_element_mass_fractions(&self) -> PyResult<HashMap<String, f64>> {
+        self.internal
+            .get_element_mass_fractions()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Electron density of the material.
+    ///
+    /// Returns:
+    ///     float: Electrons per cm3.
+    ///
+    /// Raises:
+    ///     ValueError: If the density or composition is not defined.
+    fn get_electron_density(&self) -> PyResult<f64> {
+        self.internal
+            .get_electron_density()
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Effective atomic number, (sum of a_i * Z_i^2.94)^(1/2.94) with a_i the electron fractions.
+    ///
+    /// Returns:
+    ///     float: Effective Z.
+    ///
+    /// Raises:
+    ///     ValueError: If the composition is empty.
+    fn get_effective_z(&self) -> PyResult<f64> {
+        self.internal.get_effective_z().map_err(PyValueError::new_err)
+    }
+
     /// Get the material nuclides as a tuple of (name, fraction) pairs
     #[getter]
     fn nuclides(&self) -> Vec<(String, f64)> {
@@ -103,6 +587,27 @@ impl PyMaterial {
         nuclide_vec
     }
 
+    /// Fraction type ("ao" or "wo") of each nuclide as a sorted list of (name, type) pairs.
+    #[getter]
+    fn fraction_types(&self) -> Vec<(String, String)> {
+        let mut types: Vec<(String, String)> = self
+            .internal
+            .nuclides
+            .keys()
+            .map(|k| (k.clone(), self.internal.fraction_type_of(k).as_str().to_string()))
+            .collect();
+        types.sort_by(|a, b| a.0.cmp(&b.0));
+        types
+    }
+
+    /// Normalized atom fraction per nuclide, converting any weight fractions.
+    ///
+    /// Returns:
+    ///     Dict[str, float]: nuclide -> atom fraction (sums to 1)
+    fn get_atom_fractions(&self) -> HashMap<String, f64> {
+        self.internal.get_atom_fractions()
+    }
+
     /// Material volume in cm^3, if set.
     #[getter]
     fn volume(&self) -> Option<f64> {
@@ -134,6 +639,8 @@ impl PyMaterial {
                 "  Density: {} {}\n",
                 density, self.internal.density_units
             ));
+        } else if self.internal.has_density() {
+            result.push_str("  Density: sum of nuclide densities\n");
         } else {
             result.push_str("  Density: not set\n");
         }
@@ -148,7 +655,26 @@ impl PyMaterial {
         // Add nuclide information
         result.push_str("  Composition:\n");
         for (nuclide, fraction) in &self.internal.nuclides {
-            result.push_str(&format!("    {}: {}\n", nuclide, fraction));
+            let origin = match self.internal.element_of(nuclide) {
+                Some(element)
+                    if self
+                        .internal
+                        .get_impurities()
+                        .iter()
+                        .any(|i| i.element == element) =>
+                {
+                    format!(" (impurity {})", element)
+                }
+                Some(element) => format!(" (from {})", element),
+                None => String::new(),
+            };
+            result.push_str(&format!(
+                "    {}: {} {}{}\n",
+                nuclide,
+                fraction,
+                self.internal.fraction_type_of(nuclide).as_str(),
+                origin
+            ));
         }
 
         Ok(result)
@@ -230,10 +756,36 @@ impl PyMaterial {
         self.internal.temperature.clone()
     }
 
-    /// Set current temperature label.
+    /// Set current temperature, either as a number in K (e.g. 600.0) or a
+    /// label (e.g. "600"). Temperatures between those in the nuclear data are
+    /// evaluated using `temperature_method`.
+    #[setter]
+    fn set_temperature(&mut self, temperature: &PyAny) -> PyResult<()> {
+        if let Ok(label) = temperature.extract::<String>() {
+            self.internal.set_temperature(label);
+        } else if let Ok(kelvin) = temperature.extract::<f64>() {
+            self.internal.set_temperature(kelvin);
+        } else {
+            return Err(pyo3::exceptions::PyTypeError::new_err(
+                "temperature must be a number in K or a string",
+            ));
+        }
+        Ok(())
+    }
+
+    /// How cross sections are evaluated at temperatures between those in the
+    /// nuclear data: "nearest" (default), "interpolation" or "stochastic".
+    #[getter]
+    fn temperature_method(&self) -> String {
+        self.internal.temperature_method.to_string()
+    }
+
+    /// Set the temperature method ("nearest", "interpolation" or "stochastic").
     #[setter]
-    fn set_temperature(&mut self, temperature: &str) {
-        self.internal.set_temperature(temperature);
+    fn set_temperature_method(&mut self, method: &str) -> PyResult<()> {
+        let method: TemperatureMethod = method.parse().map_err(PyValueError::new_err)?;
+        self.internal.set_temperature_method(method);
+        Ok(())
     }
 
     /// Return (and build if needed) the unified neutron energy grid.
@@ -313,15 +865,78 @@ impl PyMaterial {
         self.internal.mean_free_path_neutron(energy)
     }
 
-    /// Add a natural element by atomic fraction (expands to isotopes internally).
+    /// Add an element (expands to isotopes internally), optionally enriched.
     ///
     /// Args:
     ///     element (str): Element symbol (e.g. "Fe").
-    ///     fraction (float): Atomic fraction for the element.
-    #[pyo3(text_signature = "(self, element, fraction)")]
-    fn add_element(&mut self, element: String, fraction: f64) -> PyResult<()> {
+    ///     fraction (float): Fraction for the element.
+    ///     fraction_type (str): "ao" for atom fraction (default) or "wo" for weight fraction.
+    ///     enrichment (Optional[float]): Percent (0-100) of the enrichment target in the element.
+    ///     enrichment_target (Optional[str]): Isotope to enrich (e.g. "Li6"), required with enrichment.
+    ///     enrichment_type (str): "ao" for atom percent (default) or "wo" for weight percent.
+    ///     natural (bool): Always add the natural element evaluation (e.g. "C0")
+    ///         instead of the isotopes. Defaults to False, in which case the natural
+    ///         element is only used when the Config has data for it but none for
+    ///         its isotopes.
+    ///
+    /// Raises:
+    ///     ValueError: On invalid element, fraction or enrichment.
+    #[pyo3(signature = (element, fraction, fraction_type="ao", enrichment=None, enrichment_target=None, enrichment_type="ao", natural=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_element(
+        &mut self,
+        element: String,
+        fraction: f64,
+        fraction_type: &str,
+        enrichment: Option<f64>,
+        enrichment_target: Option<String>,
+        enrichment_type: &str,
+        natural: bool,
+    ) -> PyResult<()> {
+        let fraction_type: FractionType = fraction_type.parse().map_err(PyValueError::new_err)?;
+        let result = match (enrichment, enrichment_target) {
+            (None, None) if natural => self
+                .internal
+                .add_natural_element(&element, fraction, fraction_type),
+            (None, None) => self
+                .internal
+                .add_element_with_fraction_type(&element, fraction, fraction_type),
+            (Some(_), Some(_)) if natural => Err(String::from(
+                "natural elements cannot be enriched",
+            )),
+            (Some(enrichment), Some(target)) => {
+                let enrichment_type: FractionType =
+                    enrichment_type.parse().map_err(PyValueError::new_err)?;
+                self.internal.add_element_enriched(
+                    &element,
+                    fraction,
+                    fraction_type,
+                    &target,
+                    enrichment,
+                    enrichment_type,
+                )
+            }
+            _ => Err(String::from(
+                "enrichment and enrichment_target must be specified together",
+            )),
+        };
+        result.map_err(PyValueError::new_err)
+    }
+
+    /// Add the elements of a chemical formula (expands to isotopes internally).
+    ///
+    /// Args:
+    ///     formula (str): Chemical formula (e.g. "Li4SiO4", "Pb84.2Li15.8", "CaSO4·2H2O").
+    ///     fraction (float): Fraction for the whole compound.
+    ///     fraction_type (str): "ao" for atom fraction (default) or "wo" for weight fraction.
+    ///
+    /// Raises:
+    ///     ValueError: If the formula cannot be parsed or the fraction is invalid.
+    #[pyo3(signature = (formula, fraction, fraction_type="ao"))]
+    fn add_elements_from_formula(&mut self, formula: String, fraction: f64, fraction_type: &str) -> PyResult<()> {
+        let fraction_type: FractionType = fraction_type.parse().map_err(PyValueError::new_err)?;
         self.internal
-            .add_element(&element, fraction)
+            .add_elements_from_formula_with_fraction_type(&formula, fraction, fraction_type)
             .map_err(|e| PyValueError::new_err(e))
     }
 
@@ -393,3 +1008,30 @@ impl PyMaterial {
         PyMaterial { internal: material }
     }
 }
+
+/// Names of all materials in the built-in compendium.
+///
+/// Returns:
+///     List[str]: Material names usable with Material.from_library.
+#[pyfunction]
+pub fn material_library_names() -> Vec<String> {
+    crate::material_library::material_library_names()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Search the built-in compendium by name or description.
+///
+/// Args:
+///     query (str): Case-insensitive text to look for (e.g. "steel", "concrete").
+///
+/// Returns:
+///     Dict[str, str]: Matching material names mapped to their descriptions.
+#[pyfunction]
+pub fn search_material_library(query: &str) -> HashMap<String, String> {
+    crate::material_library::search_material_library(query)
+        .into_iter()
+        .map(|entry| (entry.name.to_string(), entry.description.to_string()))
+        .collect()
+}
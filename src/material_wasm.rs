@@ -1,4 +1,4 @@
-use crate::material::Material;
+use crate::material::{FractionType, Impurity, Material};
 use js_sys::{Array, Map, JSON};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -15,6 +15,13 @@ struct MacroscopicXsResult {
     cross_sections: HashMap<i32, Vec<f64>>,
 }
 
+fn parse_fraction_type(fraction_type: Option<String>) -> Result<FractionType, JsValue> {
+    match fraction_type {
+        Some(t) => t.parse().map_err(|e: String| JsValue::from_str(&e)),
+        None => Ok(FractionType::Atom),
+    }
+}
+
 #[wasm_bindgen]
 impl WasmMaterial {
     #[wasm_bindgen(constructor)]
@@ -24,20 +31,162 @@ impl WasmMaterial {
         }
     }
 
+    /// Create a material from the built-in compendium (e.g. "Eurofer97").
+    #[wasm_bindgen]
+    pub fn from_library(name: &str) -> Result<WasmMaterial, JsValue> {
+        Material::from_library(name)
+            .map(|inner| WasmMaterial { inner })
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Serialize the material definition (no cross section data) to a JSON string.
+    #[wasm_bindgen]
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        self.inner
+            .to_json()
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Create a material from a JSON string written by `to_json`.
+    #[wasm_bindgen]
+    pub fn from_json(json: &str) -> Result<WasmMaterial, JsValue> {
+        Material::from_json(json)
+            .map(|inner| WasmMaterial { inner })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Write the material as an OpenMC materials.xml document (material ID 1).
     #[wasm_bindgen]
-    pub fn add_nuclide(&mut self, nuclide: &str, fraction: f64) -> Result<(), JsValue> {
+    pub fn to_openmc_xml(&self) -> Result<String, JsValue> {
         self.inner
-            .add_nuclide(nuclide, fraction)
+            .to_openmc_xml()
             .map_err(|e| JsValue::from_str(&e))
     }
 
+    /// Write the material as an MCNP m card; `library` is an optional ZAID suffix (e.g. "80c").
     #[wasm_bindgen]
-    pub fn add_element(&mut self, element: &str, fraction: f64) -> Result<(), JsValue> {
+    pub fn to_mcnp(&self, number: u32, library: Option<String>) -> Result<String, JsValue> {
         self.inner
-            .add_element(element, fraction)
+            .to_mcnp(number, library.as_deref())
             .map_err(|e| JsValue::from_str(&e))
     }
 
+    /// Write the material as a Serpent mat block; `library` is an optional ZAID suffix (e.g. "03c").
+    #[wasm_bindgen]
+    pub fn to_serpent(&self, library: Option<String>) -> Result<String, JsValue> {
+        self.inner
+            .to_serpent(library.as_deref())
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Write the FISPACT-II DENSITY and MASS input; requires the volume and density.
+    #[wasm_bindgen]
+    pub fn to_fispact_mass(&self) -> Result<String, JsValue> {
+        self.inner
+            .to_fispact_mass()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Write the FISPACT-II DENSITY and FUEL input; requires the volume and density.
+    #[wasm_bindgen]
+    pub fn to_fispact_fuel(&self) -> Result<String, JsValue> {
+        self.inner
+            .to_fispact_fuel()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add a nuclide; `fraction_type` is "ao" (atom, default) or "wo" (weight).
+    #[wasm_bindgen]
+    pub fn add_nuclide(
+        &mut self,
+        nuclide: &str,
+        fraction: f64,
+        fraction_type: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = parse_fraction_type(fraction_type)?;
+        self.inner
+            .add_nuclide_with_fraction_type(nuclide, fraction, fraction_type)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add a natural element; `fraction_type` is "ao" (atom, default) or "wo" (weight).
+    #[wasm_bindgen]
+    pub fn add_element(
+        &mut self,
+        element: &str,
+        fraction: f64,
+        fraction_type: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = parse_fraction_type(fraction_type)?;
+        self.inner
+            .add_element_with_fraction_type(element, fraction, fraction_type)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add an element as its natural element evaluation (e.g. "C0"); `fraction_type` is "ao" or "wo".
+    #[wasm_bindgen]
+    pub fn add_natural_element(
+        &mut self,
+        element: &str,
+        fraction: f64,
+        fraction_type: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = parse_fraction_type(fraction_type)?;
+        self.inner
+            .add_natural_element(element, fraction, fraction_type)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add an element with `enrichment_target` (e.g. "Li6") enriched to `enrichment` percent;
+    /// `fraction_type` and `enrichment_type` are "ao" (default) or "wo".
+    #[wasm_bindgen]
+    pub fn add_element_enriched(
+        &mut self,
+        element: &str,
+        fraction: f64,
+        enrichment_target: &str,
+        enrichment: f64,
+        fraction_type: Option<String>,
+        enrichment_type: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = parse_fraction_type(fraction_type)?;
+        let enrichment_type = parse_fraction_type(enrichment_type)?;
+        self.inner
+            .add_element_enriched(
+                element,
+                fraction,
+                fraction_type,
+                enrichment_target,
+                enrichment,
+                enrichment_type,
+            )
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add the elements of a chemical formula such as "Li4SiO4"; `fraction_type` is "ao" or "wo".
+    #[wasm_bindgen]
+    pub fn add_elements_from_formula(
+        &mut self,
+        formula: &str,
+        fraction: f64,
+        fraction_type: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = parse_fraction_type(fraction_type)?;
+        self.inner
+            .add_elements_from_formula_with_fraction_type(formula, fraction, fraction_type)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Normalized atom fraction per nuclide as a JS Map, converting weight fractions.
+    #[wasm_bindgen]
+    pub fn get_atom_fractions(&self) -> Map {
+        let map = Map::new();
+        for (nuclide, fraction) in self.inner.get_atom_fractions() {
+            map.set(&JsValue::from_str(&nuclide), &JsValue::from_f64(fraction));
+        }
+        map
+    }
+
     #[wasm_bindgen]
     pub fn set_density(&mut self, unit: &str, value: f64) -> Result<(), JsValue> {
         self.inner
@@ -45,6 +194,235 @@ impl WasmMaterial {
             .map_err(|e| JsValue::from_str(&e))
     }
 
+    /// Mass density in g/cm3 whatever units the density was set in.
+    #[wasm_bindgen]
+    pub fn get_mass_density(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_mass_density()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Element symbols present in the composition, sorted alphabetically.
+    #[wasm_bindgen]
+    pub fn get_elements(&self) -> Array {
+        self.inner
+            .get_elements()
+            .into_iter()
+            .map(|e| JsValue::from_str(&e))
+            .collect()
+    }
+
+    /// Fraction of each element as it was added, as a JS Map.
+    #[wasm_bindgen]
+    pub fn get_element_fractions(&self) -> Map {
+        let map = Map::new();
+        for (element, fraction) in self.inner.get_element_fractions() {
+            map.set(&JsValue::from_str(&element), &JsValue::from_f64(fraction));
+        }
+        map
+    }
+
+    /// Element a nuclide was expanded from, or undefined if it was added directly.
+    #[wasm_bindgen]
+    pub fn element_of(&self, nuclide: &str) -> Option<String> {
+        self.inner.element_of(nuclide).map(str::to_string)
+    }
+
+    /// Nuclides added directly, sorted alphabetically.
+    #[wasm_bindgen]
+    pub fn get_direct_nuclides(&self) -> Array {
+        self.inner
+            .get_direct_nuclides()
+            .into_iter()
+            .map(|n| JsValue::from_str(&n))
+            .collect()
+    }
+
+    /// Nuclides expanded from elements, sorted alphabetically.
+    #[wasm_bindgen]
+    pub fn get_expanded_nuclides(&self) -> Array {
+        self.inner
+            .get_expanded_nuclides()
+            .into_iter()
+            .map(|n| JsValue::from_str(&n))
+            .collect()
+    }
+
+    /// Remove every isotope of an element.
+    #[wasm_bindgen]
+    pub fn remove_element(&mut self, element: &str) -> Result<(), JsValue> {
+        self.inner
+            .remove_element(element)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Replace an element with the natural isotopes of another, keeping its fraction.
+    #[wasm_bindgen]
+    pub fn replace_element(&mut self, element: &str, replacement: &str) -> Result<(), JsValue> {
+        self.inner
+            .replace_element(element, replacement)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Remove a nuclide from the composition.
+    #[wasm_bindgen]
+    pub fn remove_nuclide(&mut self, nuclide: &str) -> Result<(), JsValue> {
+        self.inner
+            .remove_nuclide(nuclide)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Replace a nuclide with another, keeping the number of atoms.
+    #[wasm_bindgen]
+    pub fn replace_nuclide(&mut self, nuclide: &str, replacement: &str) -> Result<(), JsValue> {
+        self.inner
+            .replace_nuclide(nuclide, replacement)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Multiply every fraction by a factor.
+    #[wasm_bindgen]
+    pub fn scale(&mut self, factor: f64) -> Result<(), JsValue> {
+        self.inner.scale(factor).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Multiply the fraction of one nuclide by a factor.
+    #[wasm_bindgen]
+    pub fn scale_nuclide(&mut self, nuclide: &str, factor: f64) -> Result<(), JsValue> {
+        self.inner
+            .scale_nuclide(nuclide, factor)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Scale the fractions so they sum to 1.
+    #[wasm_bindgen]
+    pub fn normalize(&mut self) -> Result<(), JsValue> {
+        self.inner.normalize().map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Replace the composition with absolute atom densities given as an object
+    /// of nuclide -> density; `units` is "atom/b-cm" (default) or "atom/cm3".
+    #[wasm_bindgen]
+    pub fn set_atom_densities(
+        &mut self,
+        densities: &JsValue,
+        units: Option<String>,
+    ) -> Result<(), JsValue> {
+        let json: String = JSON::stringify(densities)?.into();
+        let densities: HashMap<String, f64> = serde_json::from_str(&json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid atom densities: {}", e)))?;
+        self.inner
+            .set_atom_densities(&densities, units.as_deref().unwrap_or("atom/b-cm"))
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Add a trace element in ppm of the whole material; `units` is "wppm" (default) or "appm".
+    #[wasm_bindgen]
+    pub fn add_impurity(
+        &mut self,
+        element: &str,
+        ppm: f64,
+        units: Option<String>,
+    ) -> Result<(), JsValue> {
+        let fraction_type = Impurity::fraction_type_from_units(units.as_deref().unwrap_or("wppm"))
+            .map_err(|e| JsValue::from_str(&e))?;
+        self.inner
+            .add_impurity(element, ppm, fraction_type)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Remove an impurity, leaving the rest of the composition unchanged.
+    #[wasm_bindgen]
+    pub fn remove_impurity(&mut self, element: &str) -> Result<(), JsValue> {
+        self.inner
+            .remove_impurity(element)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Table of the composition showing how each nuclide was added.
+    #[wasm_bindgen]
+    pub fn composition_report(&self) -> String {
+        self.inner.composition_report()
+    }
+
+    /// Average molar mass of the atoms in g/mol.
+    #[wasm_bindgen]
+    pub fn get_average_molar_mass(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_average_molar_mass()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Total mass in grams from the volume and density.
+    #[wasm_bindgen]
+    pub fn get_total_mass(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_total_mass()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Number of atoms of each nuclide in the material volume as a JS Map.
+    #[wasm_bindgen]
+    pub fn get_atoms_per_nuclide(&self) -> Result<Map, JsValue> {
+        let atoms = self
+            .inner
+            .get_atoms_per_nuclide()
+            .map_err(|e| JsValue::from_str(&e))?;
+        let map = Map::new();
+        for (nuclide, count) in atoms {
+            map.set(&JsValue::from_str(&nuclide), &JsValue::from_f64(count));
+        }
+        Ok(map)
+    }
+
+    /// Total number of atoms in the material volume.
+    #[wasm_bindgen]
+    pub fn get_total_atoms(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_total_atoms()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Atom fraction of each element as a JS Map.
+    #[wasm_bindgen]
+    pub fn get_element_atom_fractions(&self) -> Map {
+        let map = Map::new();
+        for (element, fraction) in self.inner.get_element_atom_fractions() {
+            map.set(&JsValue::from_str(&element), &JsValue::from_f64(fraction));
+        }
+        map
+    }
+
+    /// Mass fraction of each element as a JS Map.
+    #[wasm_bindgen]
+    pub fn get_element_mass_fractions(&self) -> Result<Map, JsValue> {
+        let fractions = self
+            .inner
+            .get_element_mass_fractions()
+            .map_err(|e| JsValue::from_str(&e))?;
+        let map = Map::new();
+        for (element, fraction) in fractions {
+            map.set(&JsValue::from_str(&element), &JsValue::from_f64(fraction));
+        }
+        Ok(map)
+    }
+
+    /// Electron density in electrons/cm3.
+    #[wasm_bindgen]
+    pub fn get_electron_density(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_electron_density()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Effective atomic number (power law with exponent 2.94).
+    #[wasm_bindgen]
+    pub fn get_effective_z(&self) -> Result<f64, JsValue> {
+        self.inner
+            .get_effective_z()
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     #[wasm_bindgen]
     pub fn set_volume(&mut self, value: f64) -> Result<(), JsValue> {
         self.inner
@@ -70,7 +448,7 @@ impl WasmMaterial {
     #[wasm_bindgen]
     pub fn get_atoms_per_barn_cm(&self) -> Result<JsValue, JsValue> {
         // Safe to use try-catch pattern with WASM since panics will be converted to JS exceptions
-        if self.inner.density.is_none() {
+        if !self.inner.has_density() {
             return Err(JsValue::from_str(
                 "Cannot calculate atoms per cc: Material has no density defined",
             ));
@@ -99,7 +477,7 @@ impl WasmMaterial {
         by_nuclide: Option<bool>,
     ) -> Result<JsValue, JsValue> {
         // Check preconditions to avoid panics
-        if self.inner.density.is_none() {
+        if !self.inner.has_density() {
             return Err(JsValue::from_str(
                 "Cannot calculate macroscopic cross sections: Material has no density defined",
             ));
@@ -202,3 +580,25 @@ impl WasmMaterial {
         self.inner.sample_interacting_nuclide(energy, &mut rng)
     }
 }
+
+/// Names of all materials in the built-in compendium.
+#[wasm_bindgen]
+pub fn material_library_names() -> Array {
+    crate::material_library::material_library_names()
+        .into_iter()
+        .map(JsValue::from_str)
+        .collect::<Array>()
+}
+
+/// Search the built-in compendium by name or description, returning a Map of name -> description.
+#[wasm_bindgen]
+pub fn search_material_library(query: &str) -> Map {
+    let map = Map::new();
+    for entry in crate::material_library::search_material_library(query) {
+        map.set(
+            &JsValue::from_str(entry.name),
+            &JsValue::from_str(entry.description),
+        );
+    }
+    map
+}
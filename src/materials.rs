@@ -1,6 +1,8 @@
 use crate::config::CONFIG;
 use crate::material::Material;
-use crate::nuclide::{get_or_load_nuclide, Nuclide};
+use crate::nuclide::{get_or_load_nuclide, get_or_load_nuclide_selecting, Nuclide};
+use crate::nuclide_names::canonical_nuclide_name;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -12,11 +14,12 @@ use std::sync::Arc;
 /// materials (minimizing memory and parse cost). Methods that load nuclides
 /// compute the union of temperature requests per nuclide so subsequent loads
 /// can expand (but never shrink) the `loaded_temperatures` set.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Materials {
     /// Storage for materials in a vector
     materials: Vec<Material>,
     /// Shared cache: nuclide name -> `Arc<Nuclide>` (union of all material needs)
+    #[serde(skip)]
     pub nuclide_data: HashMap<String, Arc<Nuclide>>,
 }
 
@@ -83,12 +86,96 @@ impl Materials {
         self.materials.iter_mut()
     }
 
+    /// Serialize the material definitions to a JSON string (see [`Material::to_json`]).
+    pub fn to_json(&self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Create a materials collection from a JSON string written by [`Materials::to_json`].
+    pub fn from_json(json: &str) -> Result<Materials, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Write all materials as an OpenMC `materials.xml` document with IDs 1..=n in order.
+    pub fn to_openmc_xml(&self) -> Result<String, String> {
+        crate::openmc::materials_to_openmc_xml(&self.materials)
+    }
+
+    /// Write all materials to an OpenMC `materials.xml` file.
+    pub fn export_to_openmc_xml(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_openmc_xml()?)?;
+        Ok(())
+    }
+
+    /// Write all materials as MCNP `m` cards numbered 1..=n in order.
+    pub fn to_mcnp(&self, library: Option<&str>) -> Result<String, String> {
+        crate::mcnp::materials_to_mcnp(&self.materials, library)
+    }
+
+    /// Write all materials as MCNP `m` cards to a file.
+    pub fn export_to_mcnp(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        library: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_mcnp(library)?)?;
+        Ok(())
+    }
+
+    /// Write all materials as Serpent `mat` blocks; unnamed materials are called `m1`, `m2`, ...
+    pub fn to_serpent(&self, library: Option<&str>) -> Result<String, String> {
+        crate::serpent::materials_to_serpent(&self.materials, library)
+    }
+
+    /// Write all materials as Serpent `mat` blocks to a file.
+    pub fn export_to_serpent(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        library: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_serpent(library)?)?;
+        Ok(())
+    }
+
+    /// Create a collection from the material cards of an MCNP input deck, in order of appearance.
+    pub fn from_mcnp(deck: &str) -> Result<Materials, String> {
+        let mut materials = Materials::new();
+        for (_, material) in crate::mcnp::materials_from_mcnp(deck)? {
+            materials.append(material);
+        }
+        Ok(materials)
+    }
+
+    /// Read the materials of an MCNP input file.
+    pub fn read_mcnp(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Materials, Box<dyn std::error::Error>> {
+        let deck = std::fs::read_to_string(path)?;
+        Ok(Self::from_mcnp(&deck)?)
+    }
+
+    /// Create a collection from the text of an OpenMC `materials.xml` document.
+    pub fn from_openmc_xml(xml: &str) -> Result<Materials, String> {
+        crate::openmc::materials_from_openmc_xml(xml)
+    }
+
+    /// Read all materials from an OpenMC `materials.xml` file.
+    pub fn read_openmc_xml(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Materials, Box<dyn std::error::Error>> {
+        let xml = std::fs::read_to_string(path)?;
+        Ok(Self::from_openmc_xml(&xml)?)
+    }
+
     /// Read (and cache) all nuclides needed by materials from JSON paths, loading only the union of requested temperatures per nuclide.
     pub fn read_nuclides_from_json(
         &mut self,
         nuclide_json_map: &HashMap<String, String>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        use std::collections::{HashMap as StdHashMap, HashSet};
+        use std::collections::HashSet;
         // Collect all nuclide names across materials
         let mut merged: HashMap<String, String> = HashMap::new();
         let cfg = CONFIG.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
@@ -102,27 +189,32 @@ impl Materials {
         drop(cfg);
         // Override with provided explicit mappings
         for (k, v) in nuclide_json_map {
-            merged.insert(k.clone(), v.clone());
+            merged.insert(canonical_nuclide_name(k), v.clone());
         }
         let source_map: &HashMap<String, String> = &merged;
-        // Map nuclide -> set of requested temperatures
-        let mut requests: StdHashMap<String, HashSet<String>> = StdHashMap::new();
-        for mat in &self.materials {
-            for nuclide in mat.nuclides.keys() {
-                let entry = requests.entry(nuclide.clone()).or_insert_with(HashSet::new);
-                entry.insert(mat.temperature.clone());
-            }
-        }
-        // Load each with union temps
-        // Load in deterministic alphabetical order
-        let mut request_keys: Vec<String> = requests.keys().cloned().collect();
+        // Nuclides used by any material, loaded in deterministic alphabetical order
+        let mut request_keys: Vec<String> = self
+            .materials
+            .iter()
+            .flat_map(|mat| mat.nuclides.keys().cloned())
+            .collect();
         request_keys.sort();
+        request_keys.dedup();
         for nuclide_name in request_keys {
-            if let Some(temps) = requests.get(&nuclide_name) {
-                let arc = get_or_load_nuclide(&nuclide_name, source_map, Some(temps))?;
-                self.nuclide_data
-                    .insert(nuclide_name.clone(), Arc::clone(&arc));
-            }
+            // The union of the data temperatures each material needs, including
+            // the nearest or bracketing ones for temperatures missing from the data
+            let select = |available: &[String]| {
+                let mut required: HashSet<String> = HashSet::new();
+                for mat in &self.materials {
+                    if mat.nuclides.contains_key(&nuclide_name) {
+                        required.extend(mat.required_temperatures(available));
+                    }
+                }
+                required
+            };
+            let arc = get_or_load_nuclide_selecting(&nuclide_name, source_map, &select)?;
+            self.nuclide_data
+                .insert(nuclide_name.clone(), Arc::clone(&arc));
         }
         // Distribute arcs to materials
         for mat in &mut self.materials {
@@ -132,6 +224,10 @@ impl Materials {
                     mat.nuclide_data
                         .insert(nuclide_name.clone(), Arc::clone(shared_arc));
                 }
+                if let Some(source) = source_map.get(nuclide_name) {
+                    mat.nuclide_data_sources
+                        .insert(nuclide_name.clone(), source.clone());
+                }
             }
         }
         Ok(())
@@ -245,6 +341,11 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    // Unique per test run so concurrent runs don't share files
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("materials_for_mc_test_{}_{}", std::process::id(), name))
+    }
+
     #[test]
     fn test_new_materials() {
         let materials = Materials::new();
@@ -338,6 +439,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_read_nuclides_from_json_canonical_map_keys() {
+        let mut nuclide_json_map = HashMap::new();
+        nuclide_json_map.insert("li-6".to_string(), "tests/Li6.json".to_string());
+        let mut materials = Materials::new();
+        let mut mat = Material::new();
+        mat.add_nuclide("Li6", 1.0).unwrap();
+        materials.append(mat);
+        materials.read_nuclides_from_json(&nuclide_json_map).unwrap();
+        let mat = materials.get(0).unwrap();
+        assert_eq!(mat.nuclide_data_sources["Li6"], "tests/Li6.json");
+        assert!(mat.nuclide_data.contains_key("Li6"));
+    }
+
     #[test]
     fn test_union_temperature_loading_subset_then_union() {
         crate::nuclide::clear_nuclide_cache();
@@ -392,4 +507,110 @@ mod tests {
             "Union load with both temps requested simultaneously should load both temps"
         );
     }
+
+    #[test]
+    fn test_materials_json_round_trip() {
+        let mut materials = Materials::new();
+        let mut steel = Material::new();
+        steel.set_name("steel");
+        steel.add_element("Fe", 1.0).unwrap();
+        steel.set_density("g/cm3", 7.8).unwrap();
+        materials.append(steel);
+        let mut water = Material::new();
+        water.set_name("water");
+        water.add_elements_from_formula("H2O", 1.0).unwrap();
+        materials.append(water);
+
+        let json = materials.to_json().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["materials"].as_array().unwrap().len(), 2);
+        assert!(value.get("nuclide_data").is_none());
+
+        let restored = Materials::from_json(&json).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.get(0).unwrap().get_name(), Some("steel"));
+        assert_eq!(restored.get(0).unwrap().density, Some(7.8));
+        assert_eq!(restored.get(1).unwrap().nuclides, materials.get(1).unwrap().nuclides);
+        assert_eq!(restored.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_materials_export_to_openmc_xml() {
+        let mut materials = Materials::new();
+        for (name, element) in [("lithium", "Li"), ("iron", "Fe")] {
+            let mut material = Material::new();
+            material.set_name(name);
+            material.add_element(element, 1.0).unwrap();
+            material.set_density("g/cm3", 1.0).unwrap();
+            materials.append(material);
+        }
+        let path = temp_path("materials.xml");
+        materials.export_to_openmc_xml(&path).unwrap();
+        let xml = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(xml.contains("<material id=\"1\" name=\"lithium\" temperature=\"294\">"));
+        assert!(xml.contains("<material id=\"2\" name=\"iron\" temperature=\"294\">"));
+        assert!(xml.contains("<nuclide name=\"Li7\" ao=\"0.92411\" />"));
+        assert_eq!(xml, materials.to_openmc_xml().unwrap());
+    }
+
+    #[test]
+    fn test_materials_read_openmc_xml() {
+        let mut material = Material::new();
+        material.set_name("beryllium");
+        material.add_element("Be", 1.0).unwrap();
+        material.set_density("g/cm3", 1.85).unwrap();
+        let mut materials = Materials::new();
+        materials.append(material);
+        let path = temp_path("read_materials.xml");
+        materials.export_to_openmc_xml(&path).unwrap();
+        let restored = Materials::read_openmc_xml(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.get(0).unwrap().get_name(), Some("beryllium"));
+        assert_eq!(restored.get(0).unwrap().nuclides["Be9"], 1.0);
+        assert!(Materials::read_openmc_xml("missing_materials.xml").is_err());
+    }
+
+    #[test]
+    fn test_materials_mcnp_export_and_read() {
+        let mut materials = Materials::new();
+        for (name, element, density) in [("lithium", "Li", 0.534), ("iron", "Fe", 7.8)] {
+            let mut material = Material::new();
+            material.set_name(name);
+            material.add_element(element, 1.0).unwrap();
+            material.set_density("g/cm3", density).unwrap();
+            materials.append(material);
+        }
+        let path = temp_path("materials.mcnp");
+        materials.export_to_mcnp(&path, Some("80c")).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("m1    3006.80c"));
+        assert!(text.contains("m2    26054.80c"));
+        let restored = Materials::read_mcnp(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.get(1).unwrap().get_name(), Some("iron"));
+        assert_eq!(restored.get(1).unwrap().nuclides, materials.get(1).unwrap().nuclides);
+        assert_eq!(restored.get(1).unwrap().density, Some(7.8));
+    }
+
+    #[test]
+    fn test_materials_export_to_serpent() {
+        let mut materials = Materials::new();
+        for name in ["first", "second"] {
+            let mut material = Material::new();
+            material.set_name(name);
+            material.add_nuclide("Be9", 1.0).unwrap();
+            material.set_density("g/cm3", 1.85).unwrap();
+            materials.append(material);
+        }
+        let path = temp_path("materials.serpent");
+        materials.export_to_serpent(&path, Some("03c")).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(text, materials.to_serpent(Some("03c")).unwrap());
+        assert!(text.contains("mat first -1.85\n4009.03c     1\n"));
+        assert!(text.contains("mat second -1.85\n"));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::material_python::PyMaterial;
 use crate::materials::Materials;
-use pyo3::exceptions::PyIndexError;
+use pyo3::exceptions::{PyIndexError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyList;
 
@@ -32,6 +32,172 @@ impl PyMaterials {
         Ok(result)
     }
 
+    /// Serialize all material definitions to a JSON string.
+    ///
+    /// Returns:
+    ///     str: JSON representation of the materials.
+    fn to_json(&self) -> PyResult<String> {
+        self.internal
+            .to_json()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a materials collection from a JSON string written by to_json.
+    ///
+    /// Args:
+    ///     json (str): JSON representation of the materials.
+    ///
+    /// Returns:
+    ///     Materials: The restored collection.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<PyMaterials> {
+        let internal = Materials::from_json(json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyMaterials { internal })
+    }
+
+    /// Write all materials as an OpenMC materials.xml document with IDs 1..n in order.
+    ///
+    /// Returns:
+    ///     str: XML text.
+    fn to_openmc_xml(&self) -> PyResult<String> {
+        self.internal.to_openmc_xml().map_err(PyValueError::new_err)
+    }
+
+    /// Write all materials to an OpenMC materials.xml file.
+    ///
+    /// Args:
+    ///     path (str): Output file path (e.g. "materials.xml").
+    #[pyo3(signature = (path="materials.xml"))]
+    fn export_to_openmc_xml(&self, path: &str) -> PyResult<()> {
+        self.internal
+            .export_to_openmc_xml(path)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write all materials as MCNP m cards numbered 1..n in order.
+    ///
+    /// Args:
+    ///     library (str, optional): ZAID library suffix (e.g. "80c").
+    ///
+    /// Returns:
+    ///     str: The m cards.
+    #[pyo3(signature = (library=None))]
+    fn to_mcnp(&self, library: Option<&str>) -> PyResult<String> {
+        self.internal.to_mcnp(library).map_err(PyValueError::new_err)
+    }
+
+    /// Write all materials as MCNP m cards to a file.
+    ///
+    /// Args:
+    ///     path (str): Output file path.
+    ///     library (str, optional): ZAID library suffix (e.g. "80c").
+    #[pyo3(signature = (path, library=None))]
+    fn export_to_mcnp(&self, path: &str, library: Option<&str>) -> PyResult<()> {
+        self.internal
+            .export_to_mcnp(path, library)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write all materials as Serpent mat blocks.
+    ///
+    /// Args:
+    ///     library (str, optional): ZAID library suffix (e.g. "03c").
+    ///
+    /// Returns:
+    ///     str: The mat blocks; unnamed materials are called m1, m2, ...
+    ///
+    /// Raises:
+    ///     ValueError: If material names are not unique or a material is incomplete.
+    #[pyo3(signature = (library=None))]
+    fn to_serpent(&self, library: Option<&str>) -> PyResult<String> {
+        self.internal.to_serpent(library).map_err(PyValueError::new_err)
+    }
+
+    /// Write all materials as Serpent mat blocks to a file.
+    ///
+    /// Args:
+    ///     path (str): Output file path.
+    ///     library (str, optional): ZAID library suffix (e.g. "03c").
+    #[pyo3(signature = (path, library=None))]
+    fn export_to_serpent(&self, path: &str, library: Option<&str>) -> PyResult<()> {
+        self.internal
+            .export_to_serpent(path, library)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Create a materials collection from the m cards of an MCNP input deck.
+    ///
+    /// Positive fractions are read as atom fractions and negative ones as weight
+    /// fractions. Densities are taken from the cell cards of a complete deck.
+    ///
+    /// Args:
+    ///     deck (str): Text of the MCNP input (a full deck or just material cards).
+    ///
+    /// Returns:
+    ///     Materials: The materials in order of appearance.
+    ///
+    /// Raises:
+    ///     ValueError: If a material card is invalid.
+    #[staticmethod]
+    fn from_mcnp(deck: &str) -> PyResult<PyMaterials> {
+        let internal = Materials::from_mcnp(deck).map_err(PyValueError::new_err)?;
+        Ok(PyMaterials { internal })
+    }
+
+    /// Read the materials of an MCNP input file.
+    ///
+    /// Args:
+    ///     path (str): Path of the MCNP input file.
+    ///
+    /// Returns:
+    ///     Materials: The materials in order of appearance.
+    ///
+    /// Raises:
+    ///     ValueError: If the file cannot be read or a material card is invalid.
+    #[staticmethod]
+    fn read_mcnp(path: &str) -> PyResult<PyMaterials> {
+        let internal =
+            Materials::read_mcnp(path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyMaterials { internal })
+    }
+
+    /// Create a materials collection from the text of an OpenMC materials.xml file.
+    ///
+    /// Nuclides and elements (including enrichment) with atom ("ao") or weight
+    /// ("wo") fractions, densities, temperatures and volumes are read.
+    ///
+    /// Args:
+    ///     xml (str): Text of an OpenMC materials.xml document.
+    ///
+    /// Returns:
+    ///     Materials: The materials in document order.
+    ///
+    /// Raises:
+    ///     ValueError: If the XML is invalid or a material cannot be built.
+    #[staticmethod]
+    fn from_openmc_xml(xml: &str) -> PyResult<PyMaterials> {
+        let internal = Materials::from_openmc_xml(xml).map_err(PyValueError::new_err)?;
+        Ok(PyMaterials { internal })
+    }
+
+    /// Read a materials collection from an OpenMC materials.xml file.
+    ///
+    /// Args:
+    ///     path (str): Path of the materials.xml file.
+    ///
+    /// Returns:
+    ///     Materials: The materials in document order.
+    ///
+    /// Raises:
+    ///     ValueError: If the file cannot be read or parsed.
+    #[staticmethod]
+    #[pyo3(signature = (path="materials.xml"))]
+    fn read_openmc_xml(path: &str) -> PyResult<PyMaterials> {
+        let internal =
+            Materials::read_openmc_xml(path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyMaterials { internal })
+    }
+
     /// Append a material to the collection
     fn append(&mut self, material: &PyMaterial) -> PyResult<()> {
         // Use get_internal() method instead of directly accessing the field
@@ -0,0 +1,478 @@
+// Provides conversion of materials to and from MCNP material (m) cards
+use crate::material::{FractionType, Material};
+use crate::nuclide_names::{to_zaid, zaid_to_id};
+use std::collections::HashMap;
+
+/// Convert a nuclide name (e.g. `"Fe56"`, `"Am242_m1"`, `"C0"`) to an MCNP ZAID.
+///
+/// The ZAID is `Z * 1000 + A`, with `A = 0` for natural elements. Metastable
+/// states follow the MCNP convention `Z * 1000 + A + 300 + 100 * m`, with
+/// `"Am242_m1"` as 95242 and `"Am242"` as 95642. See
+/// [`crate::nuclide_names::to_zaid`].
+pub fn nuclide_to_zaid(nuclide: &str) -> Result<u32, String> {
+    to_zaid(nuclide)
+        .map_err(|e| format!("Cannot convert nuclide '{}' to an MCNP ZAID: {}", nuclide, e))
+}
+
+/// Convert an MCNP ZAID (e.g. `"26056.80c"`, `"95242"`) to a nuclide name.
+///
+/// Library suffixes are ignored and metastable ZAIDs are decoded to names such
+/// as `"Am242_m1"`. Natural element ZAIDs (e.g. `"6000"`) are returned as the
+/// element symbol.
+pub fn zaid_to_nuclide(zaid: &str) -> Result<String, String> {
+    let id = zaid_to_id(zaid)?;
+    if id.is_natural() {
+        Ok(id.symbol().to_string())
+    } else {
+        Ok(id.name())
+    }
+}
+
+/// MCNP cell density for a material: positive in atom/b-cm, negative in g/cm3.
+///
+/// Mass densities are given as negative g/cm3 values, atom densities and
+/// "sum" densities as positive atom/b-cm values.
+pub fn mcnp_cell_density(material: &Material) -> Result<f64, String> {
+    if !material.has_density() {
+        return Err(String::from("Material has no density defined"));
+    }
+    match (material.density_units.as_str(), material.density) {
+        ("atom/b-cm", Some(density)) => Ok(density),
+        ("atom/cm3", Some(density)) => Ok(density * 1.0e-24),
+        ("sum", _) => {
+            if material.nuclides.is_empty() {
+                return Err(String::from("Material has no nuclides defined"));
+            }
+            Ok(material.get_atoms_per_barn_cm().values().sum())
+        }
+        _ => Ok(-material.get_mass_density()?),
+    }
+}
+
+/// Write a material as an MCNP `m` card with the given material number.
+///
+/// Nuclides are written as ZAIDs, with `library` (e.g. `"80c"`) appended as
+/// the suffix when given. Compositions entirely in weight fractions are
+/// written as negative weight fractions, otherwise as positive atom fractions
+/// (materials with "sum" densities are written as atom densities). The card is
+/// preceded by comments with the material name and the matching cell density.
+pub fn material_to_mcnp(
+    material: &Material,
+    number: u32,
+    library: Option<&str>,
+) -> Result<String, String> {
+    if material.nuclides.is_empty() {
+        return Err(format!("Material {} has no nuclides defined", number));
+    }
+    let weight_count = material
+        .nuclides
+        .keys()
+        .filter(|n| material.fraction_type_of(n) == FractionType::Weight)
+        .count();
+    let fractions: HashMap<String, f64> = if material.density_units == "sum" {
+        material.get_atoms_per_barn_cm()
+    } else if weight_count == material.nuclides.len() {
+        material.nuclides.iter().map(|(n, &f)| (n.clone(), -f)).collect()
+    } else if weight_count > 0 {
+        material.get_atom_fractions()
+    } else {
+        material.nuclides.clone()
+    };
+
+    let suffix = match library.map(|l| l.trim_start_matches('.')) {
+        Some(l) if !l.is_empty() => format!(".{}", l),
+        _ => String::new(),
+    };
+
+    let mut card = String::new();
+    match material.get_name() {
+        Some(name) => card.push_str(&format!("c Material {}: {}\n", number, name)),
+        None => card.push_str(&format!("c Material {}\n", number)),
+    }
+    if let Ok(density) = mcnp_cell_density(material) {
+        card.push_str(&format!("c cell density: {:e}\n", density));
+    }
+    for impurity in material.get_impurities() {
+        card.push_str(&format!("c impurity {}\n", impurity));
+    }
+    let prefix = format!("m{}", number);
+    let indent = prefix.len().max(5) + 1;
+    for (index, nuclide) in material.get_nuclides().iter().enumerate() {
+        let zaid = format!("{}{}", nuclide_to_zaid(nuclide)?, suffix);
+        let start = if index == 0 { prefix.as_str() } else { "" };
+        card.push_str(&format!(
+            "{:<indent$}{:<12} {:e}\n",
+            start,
+            zaid,
+            fractions[nuclide],
+            indent = indent
+        ));
+    }
+    Ok(card)
+}
+
+/// Write materials as MCNP `m` cards numbered 1, 2, 3, ... in order.
+pub fn materials_to_mcnp<'a, I>(materials: I, library: Option<&str>) -> Result<String, String>
+where
+    I: IntoIterator<Item = &'a Material>,
+{
+    let mut cards = String::new();
+    for (index, material) in materials.into_iter().enumerate() {
+        cards.push_str(&material_to_mcnp(material, index as u32 + 1, library)?);
+    }
+    Ok(cards)
+}
+
+/// A logical MCNP card with its continuation lines joined and comments removed,
+/// together with the comment lines directly preceding it.
+struct Card {
+    tokens: Vec<String>,
+    comments: Vec<String>,
+}
+
+fn is_comment_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    indent < 5
+        && (trimmed.eq_ignore_ascii_case("c")
+            || ((trimmed.starts_with('c') || trimmed.starts_with('C'))
+                && trimmed[1..].starts_with(char::is_whitespace)))
+}
+
+/// Split lines into logical cards, joining continuation lines (indented by at
+/// least five columns or following a line ending in `&`).
+fn read_cards<'a>(lines: impl IntoIterator<Item = &'a str>) -> Vec<Card> {
+    let mut cards: Vec<Card> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    let mut continued = false;
+    for line in lines {
+        if is_comment_line(line) {
+            comments.push(line.trim_start()[1..].trim().to_string());
+            continue;
+        }
+        let content = line.split('$').next().unwrap_or_default();
+        let is_continuation = continued || content.starts_with("     ");
+        let content = content.trim_end();
+        continued = content.ends_with('&');
+        let content = content.trim_end_matches('&');
+        let tokens = content.split_whitespace().map(str::to_string);
+        match cards.last_mut() {
+            Some(card) if is_continuation => card.tokens.extend(tokens),
+            _ => cards.push(Card {
+                tokens: tokens.collect(),
+                comments: std::mem::take(&mut comments),
+            }),
+        }
+    }
+    cards
+}
+
+/// Material number of an `m` card (e.g. `"m12"`), or `None` for other cards.
+fn material_card_number(token: &str) -> Option<u32> {
+    let rest = token.strip_prefix(['m', 'M'])?;
+    if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    rest.parse().ok()
+}
+
+/// Build a material from the entries of an `m` card.
+fn material_from_card(number: u32, card: &Card) -> Result<Material, String> {
+    let mut material = Material::new();
+    for comment in &card.comments {
+        if let Some(name) = comment.strip_prefix(&format!("Material {}:", number)) {
+            if !name.trim().is_empty() {
+                material.set_name(name.trim());
+            }
+        }
+    }
+
+    // Keyword entries such as nlib=80c are not part of the composition
+    let entries: Vec<&String> = card.tokens[1..]
+        .iter()
+        .filter(|t| !t.contains('='))
+        .collect();
+    if entries.is_empty() || !entries.len().is_multiple_of(2) {
+        return Err(format!(
+            "Material card m{} must contain ZAID and fraction pairs",
+            number
+        ));
+    }
+    let mut fraction_type = None;
+    for pair in entries.chunks(2) {
+        let id = zaid_to_id(pair[0])?;
+        let fraction: f64 = pair[1].parse().map_err(|_| {
+            format!(
+                "Invalid fraction '{}' for ZAID '{}' in material card m{}",
+                pair[1], pair[0], number
+            )
+        })?;
+        let this_type = if fraction < 0.0 {
+            FractionType::Weight
+        } else {
+            FractionType::Atom
+        };
+        if fraction_type.is_some_and(|t| t != this_type) {
+            return Err(format!(
+                "Material card m{} mixes atom (positive) and weight (negative) fractions",
+                number
+            ));
+        }
+        fraction_type = Some(this_type);
+        let result = if id.is_natural() {
+            // Natural element ZAIDs refer to the natural element evaluation (e.g. C0)
+            material.add_natural_element(id.symbol(), fraction.abs(), this_type)
+        } else {
+            material.add_nuclide_with_fraction_type(id.name(), fraction.abs(), this_type)
+        };
+        result.map_err(|e| format!("Material card m{}: {}", number, e))?;
+    }
+    Ok(material)
+}
+
+/// Read the cell densities of each material from MCNP cell cards, keeping the
+/// first density found for each material number.
+fn cell_densities(cards: &[Card]) -> HashMap<u32, f64> {
+    let mut densities = HashMap::new();
+    for card in cards {
+        if card.tokens.len() < 3 || card.tokens[0].parse::<u32>().is_err() {
+            continue;
+        }
+        let material = card.tokens[1].parse::<u32>();
+        let density = card.tokens[2].parse::<f64>();
+        let (Ok(material), Ok(density)) = (material, density) else {
+            continue;
+        };
+        if material > 0 {
+            densities.entry(material).or_insert(density);
+        }
+    }
+    densities
+}
+
+/// Read the materials defined by `m` cards in an MCNP input deck.
+///
+/// Returns each material number with its material, in the order they appear.
+/// ZAIDs with library suffixes and metastable ZAIDs are converted to nuclide
+/// names; natural element ZAIDs become natural element nuclides (`6000` is
+/// `C0`), as written by [`material_to_mcnp`]. Positive
+/// fractions are read as atom fractions and negative ones as weight fractions.
+///
+/// For a complete deck (title, cell, surface and data blocks separated by blank
+/// lines) the density of the first cell using each material is applied,
+/// positive values as atom/b-cm and negative values as g/cm3. Otherwise the
+/// "cell density" comment written by [`material_to_mcnp`] is used if present.
+pub fn materials_from_mcnp(deck: &str) -> Result<Vec<(u32, Material)>, String> {
+    let lines: Vec<&str> = deck.lines().collect();
+    let mut blocks: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in &lines {
+        if line.trim().is_empty() {
+            if !blocks.last().unwrap().is_empty() {
+                blocks.push(Vec::new());
+            }
+        } else {
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+    blocks.retain(|b| !b.is_empty());
+
+    let (cells, data): (Vec<Card>, Vec<Card>) = if blocks.len() >= 3 {
+        // The first line of the first block is the title card
+        (
+            read_cards(blocks[0].iter().skip(1).copied()),
+            read_cards(blocks[2..].iter().flatten().copied()),
+        )
+    } else {
+        (Vec::new(), read_cards(lines.iter().copied()))
+    };
+    let densities = cell_densities(&cells);
+
+    let mut materials: Vec<(u32, Material)> = Vec::new();
+    for card in &data {
+        let Some(number) = card.tokens.first().and_then(|t| material_card_number(t)) else {
+            continue;
+        };
+        if materials.iter().any(|(n, _)| *n == number) {
+            return Err(format!("Material card m{} is defined more than once", number));
+        }
+        let mut material = material_from_card(number, card)?;
+
+        let density = densities.get(&number).copied().or_else(|| {
+            card.comments.iter().find_map(|c| {
+                c.strip_prefix("cell density:")
+                    .and_then(|v| v.trim().parse::<f64>().ok())
+            })
+        });
+        match density {
+            Some(d) if d < 0.0 => material.set_density("g/cm3", -d)?,
+            Some(d) if d > 0.0 => material.set_density("atom/b-cm", d)?,
+            _ => {}
+        }
+        materials.push((number, material));
+    }
+    Ok(materials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nuclide_to_zaid() {
+        assert_eq!(nuclide_to_zaid("H1").unwrap(), 1001);
+        assert_eq!(nuclide_to_zaid("Fe56").unwrap(), 26056);
+        assert_eq!(nuclide_to_zaid("U235").unwrap(), 92235);
+        assert_eq!(nuclide_to_zaid("Am242_m1").unwrap(), 95242);
+        assert_eq!(nuclide_to_zaid("Am242").unwrap(), 95642);
+        assert_eq!(nuclide_to_zaid("C0").unwrap(), 6000);
+        assert!(nuclide_to_zaid("Xx1").is_err());
+        assert!(nuclide_to_zaid("Fe").is_err());
+    }
+
+    #[test]
+    fn test_zaid_to_nuclide() {
+        assert_eq!(zaid_to_nuclide("1001.80c").unwrap(), "H1");
+        assert_eq!(zaid_to_nuclide("26056").unwrap(), "Fe56");
+        assert_eq!(zaid_to_nuclide("95242.00c").unwrap(), "Am242_m1");
+        assert_eq!(zaid_to_nuclide("95642.00c").unwrap(), "Am242");
+        assert_eq!(zaid_to_nuclide("6000.80c").unwrap(), "C");
+        assert!(zaid_to_nuclide("abc").is_err());
+        assert!(zaid_to_nuclide("200056").is_err());
+    }
+
+    #[test]
+    fn test_material_to_mcnp_atom_fractions() {
+        let mut material = Material::new();
+        material.set_name("water");
+        material.add_nuclide("H1", 2.0).unwrap();
+        material.add_nuclide("O16", 1.0).unwrap();
+        material.set_density("g/cm3", 1.0).unwrap();
+        let card = material_to_mcnp(&material, 3, Some("80c")).unwrap();
+        assert_eq!(
+            card,
+            "c Material 3: water\n\
+             c cell density: -1e0\n\
+             m3    1001.80c     2e0\n      \
+             8016.80c     1e0\n"
+        );
+    }
+
+    #[test]
+    fn test_material_to_mcnp_weight_fractions_and_density() {
+        let mut material = Material::new();
+        material
+            .add_nuclide_with_fraction_type("Fe56", 0.9, FractionType::Weight)
+            .unwrap();
+        material
+            .add_nuclide_with_fraction_type("Cr52", 0.1, FractionType::Weight)
+            .unwrap();
+        material.set_density("atom/b-cm", 0.08).unwrap();
+        let card = material_to_mcnp(&material, 1, None).unwrap();
+        assert!(card.contains("24052        -1e-1\n"));
+        assert!(card.contains("26056        -9e-1\n"));
+        assert_eq!(mcnp_cell_density(&material).unwrap(), 0.08);
+
+        // Mixed fraction types are converted to atom fractions
+        material.add_nuclide("H1", 0.1).unwrap();
+        let card = material_to_mcnp(&material, 1, None).unwrap();
+        assert!(!card.contains(" -"));
+    }
+
+    #[test]
+    fn test_material_to_mcnp_impurity_comments() {
+        let mut material = Material::new();
+        material.add_element("Fe", 1.0).unwrap();
+        material.add_impurity("Co", 5.0, FractionType::Atom).unwrap();
+        material.set_density("g/cm3", 7.8).unwrap();
+        let card = material_to_mcnp(&material, 1, None).unwrap();
+        assert!(card.contains("c impurity Co 5 appm\n"));
+        let materials = materials_from_mcnp(&card).unwrap();
+        assert_eq!(materials[0].1.nuclides.len(), material.nuclides.len());
+    }
+
+    #[test]
+    fn test_mcnp_round_trip() {
+        let mut steel = Material::new();
+        steel.set_name("steel");
+        steel
+            .add_element_with_fraction_type("Fe", 0.98, FractionType::Weight)
+            .unwrap();
+        steel
+            .add_element_with_fraction_type("C", 0.02, FractionType::Weight)
+            .unwrap();
+        steel.set_density("g/cm3", 7.8).unwrap();
+        let mut lithium = Material::new();
+        lithium.add_element("Li", 1.0).unwrap();
+        lithium.set_density("atom/b-cm", 0.046).unwrap();
+
+        let cards = materials_to_mcnp([&steel, &lithium], Some("31c")).unwrap();
+        let materials = materials_from_mcnp(&cards).unwrap();
+        assert_eq!(materials.len(), 2);
+        assert_eq!(materials[0].0, 1);
+        assert_eq!(materials[1].0, 2);
+        let restored = &materials[0].1;
+        assert_eq!(restored.get_name(), Some("steel"));
+        assert_eq!(restored.nuclides, steel.nuclides);
+        assert_eq!(restored.fraction_types, steel.fraction_types);
+        assert_eq!(restored.density, Some(7.8));
+        assert_eq!(restored.density_units, "g/cm3");
+        assert_eq!(materials[1].1.nuclides, lithium.nuclides);
+        assert_eq!(materials[1].1.density, Some(0.046));
+        assert_eq!(materials[1].1.density_units, "atom/b-cm");
+
+        let mut graphite = Material::new();
+        graphite.add_natural_element("C", 1.0, FractionType::Atom).unwrap();
+        let cards = material_to_mcnp(&graphite, 1, Some("80c")).unwrap();
+        assert!(cards.contains("6000.80c"), "{}", cards);
+        let materials = materials_from_mcnp(&cards).unwrap();
+        assert_eq!(materials[0].1.nuclides, graphite.nuclides);
+    }
+
+    #[test]
+    fn test_materials_from_mcnp_deck() {
+        let deck = "Test deck title
+c cells
+1 10 -7.8 -1 imp:n=1
+2 20 0.1 1 -2 imp:n=1 $ breeder
+3 0 2 imp:n=0
+
+1 so 10
+2 so 20
+
+mode n
+c Eurofer
+m10 26056.80c -0.9 24052.80c
+     -0.09 6000.80c -0.01
+m20 nlib=80c 3006 0.6 &
+    3007 0.4
+mt10 fe56.40t
+";
+        let materials = materials_from_mcnp(deck).unwrap();
+        assert_eq!(materials.len(), 2);
+        let (number, steel) = &materials[0];
+        assert_eq!(*number, 10);
+        assert_eq!(steel.get_name(), None);
+        assert_eq!(steel.nuclides["Fe56"], 0.9);
+        assert_eq!(steel.fraction_type_of("Cr52"), FractionType::Weight);
+        assert_eq!(steel.nuclides["C0"], 0.01);
+        assert_eq!(steel.element_of("C0"), Some("C"));
+        assert_eq!(steel.density, Some(7.8));
+        assert_eq!(steel.density_units, "g/cm3");
+
+        let (number, breeder) = &materials[1];
+        assert_eq!(*number, 20);
+        assert_eq!(breeder.nuclides["Li6"], 0.6);
+        assert_eq!(breeder.nuclides["Li7"], 0.4);
+        assert_eq!(breeder.density, Some(0.1));
+        assert_eq!(breeder.density_units, "atom/b-cm");
+    }
+
+    #[test]
+    fn test_materials_from_mcnp_errors() {
+        assert!(materials_from_mcnp("m1 1001 1.0 8016").is_err());
+        assert!(materials_from_mcnp("m1 1001 1.0 8016 -1.0").is_err());
+        assert!(materials_from_mcnp("m1 1001 1.0\nm1 8016 1.0").is_err());
+        assert!(materials_from_mcnp("m1 999999 1.0").is_err());
+        assert!(materials_from_mcnp("mode n").unwrap().is_empty());
+    }
+}
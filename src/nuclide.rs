@@ -1,15 +1,22 @@
 // Struct representing a nuclide, matching the JSON file structure
 // Update the fields as needed to match all JSON entries
+use crate::nuclide_id::NuclideId;
+use crate::nuclide_names::canonical_nuclide_name;
 use crate::reaction::Reaction;
+use crate::temperature::{
+    broadening_base_temperature, default_temperatures, parse_temperature, TemperatureMethod,
+};
+use crate::validation::ValidationReport;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+/// MT numbers of fission channels, a nuclide with any of them is fissionable.
+pub(crate) const FISSION_MTS: [i32; 5] = [18, 19, 20, 21, 38];
+
 // Global cache for nuclides to avoid reloading
 static GLOBAL_NUCLIDE_CACHE: Lazy<Mutex<HashMap<String, Arc<Nuclide>>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
@@ -79,6 +86,10 @@ pub struct Nuclide {
     pub neutron_number: Option<u32>,
     /// Mass number A.
     pub mass_number: Option<u32>,
+    /// Metastable state (0 for the ground state).
+    pub metastable: Option<u32>,
+    /// Ratio of the nuclide mass to the neutron mass (AWR).
+    pub atomic_weight_ratio: Option<f64>,
     /// Origin / library identifier (e.g. JEFF, ENDF, custom tag).
     pub library: Option<String>,
     /// Top‑level energy grid per temperature (full grid; per‑reaction grids may be threshold‑truncated).
@@ -91,6 +102,10 @@ pub struct Nuclide {
     /// All temperatures present in the JSON file regardless of filtering.
     #[serde(skip, default)]
     pub available_temperatures: Vec<String>, // All temps listed in the JSON (even if not loaded)
+    /// Temperatures listed in the data's `temperatures` that have no reaction
+    /// data or energy grid in it.
+    #[serde(skip, default)]
+    pub temperatures_without_data: Vec<String>,
     /// Subset of temperatures actually loaded into `reactions` / `energy`.
     #[serde(skip, default)]
     pub loaded_temperatures: Vec<String>, // Subset actually loaded into reactions/energy
@@ -172,6 +187,26 @@ impl Nuclide {
         // Non-elastic selection as fallback
         temp_reactions.get(&nonelastic_mt)
     }
+    /// Parsed identifier (Z, A, metastable state) of the nuclide, from its name
+    /// or, when the name is missing, its atomic, mass and metastable numbers.
+    pub fn nuclide_id(&self) -> Option<NuclideId> {
+        match self.name.as_deref() {
+            Some(name) => NuclideId::parse(name).ok(),
+            None => NuclideId::new(
+                self.atomic_number?,
+                self.mass_number?,
+                self.metastable.unwrap_or(0),
+            )
+            .ok(),
+        }
+    }
+
+    /// Atomic mass in g/mol (atomic mass units) from the atomic weight ratio.
+    pub fn atomic_mass(&self) -> Option<f64> {
+        self.atomic_weight_ratio
+            .map(|awr| awr * crate::data::NEUTRON_MASS)
+    }
+
     /// Get the energy grid for a specific temperature
     pub fn energy_grid(&self, temperature: &str) -> Option<&Vec<f64>> {
         self.energy
@@ -237,16 +272,7 @@ impl Nuclide {
     where
         R: Into<ReactionIdentifier>,
     {
-        // Convert the reaction parameter to an MT number
-        let mt = match reaction.into() {
-            ReactionIdentifier::Mt(mt_num) => mt_num,
-            ReactionIdentifier::Name(name) => {
-                // Use the REACTION_MT mapping to convert string to MT number
-                crate::data::REACTION_MT.get(name.as_str())
-                    .copied()
-                    .ok_or_else(|| format!("Unknown reaction name '{}'. Available reactions can be found in REACTION_MT mapping.", name))?
-            }
-        };
+        let mt = reaction_mt(reaction)?;
         // Check if we need to load data automatically
         if self.loaded_temperatures.is_empty() {
             // No data loaded yet - try to load it automatically
@@ -281,6 +307,189 @@ impl Nuclide {
         self.get_microscopic_cross_section_data(mt, temperature)
     }
 
+    /// Get microscopic cross section data for a reaction at a temperature in K.
+    /// Returns a tuple of (cross_section_values, energy_grid).
+    ///
+    /// Unlike [`Nuclide::microscopic_cross_section`] the temperature need not
+    /// be one present in the data: `method` chooses between the nearest
+    /// temperature, linear interpolation between the bracketing temperatures
+    /// (on the union of their energy grids) or Doppler broadening (see
+    /// [`Nuclide::doppler_broaden`]). The stochastic method gives its expected
+    /// value, which is the interpolated cross section.
+    /// Missing temperatures are loaded automatically using the nuclide name and config.
+    ///
+    /// # Arguments
+    /// * `reaction` - Either an MT number (i32) or reaction name (String/&str) like "(n,gamma)" or "fission"
+    /// * `temperature` - Temperature in K
+    /// * `method` - How to evaluate temperatures between those in the data
+    pub fn microscopic_cross_section_at_temperature<R>(
+        &mut self,
+        reaction: R,
+        temperature: f64,
+        method: TemperatureMethod,
+    ) -> Result<(Vec<f64>, Vec<f64>), Box<dyn std::error::Error>>
+    where
+        R: Into<ReactionIdentifier>,
+    {
+        let mt = reaction_mt(reaction)?;
+        if self.loaded_temperatures.is_empty() {
+            if let Some(name) = self.name.clone() {
+                self.auto_load_from_config(&name, None)?;
+            } else {
+                return Err("No data loaded and no nuclide name available for automatic loading".into());
+            }
+        }
+
+        for temp in method.required_temperatures(&self.available_temperatures, temperature)? {
+            if !self.loaded_temperatures.contains(&temp) {
+                if let Some(name) = self.name.clone() {
+                    self.auto_load_additional_temperature(&name, &temp)?;
+                }
+            }
+        }
+
+        if method == TemperatureMethod::Broadening {
+            self.doppler_broaden(temperature)?;
+        }
+
+        let loaded: Vec<String> = self.reactions.keys().cloned().collect();
+        let selected = method.weights(&loaded, temperature)?;
+        if let [(temp, _)] = selected.as_slice() {
+            return self.get_microscopic_cross_section_data(mt, Some(temp));
+        }
+
+        // Interpolate in temperature on the union of the bracketing energy grids,
+        // following each reaction's interpolation laws in energy
+        let data = selected
+            .iter()
+            .map(|(temp, weight)| {
+                self.get_microscopic_cross_section_data(mt, Some(temp))
+                    .map(|(_, energy)| (&self.reactions[temp][&mt], energy, *weight))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut energy: Vec<f64> = data.iter().flat_map(|(_, e, _)| e.iter().copied()).collect();
+        energy.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        energy.dedup();
+        let xs = energy
+            .iter()
+            .map(|&e| {
+                data.iter()
+                    .filter(|(_, grid, _)| e >= grid[0])
+                    .map(|(reaction, grid, weight)| weight * reaction.cross_section_on_grid(grid, e))
+                    .sum()
+            })
+            .collect();
+        Ok((xs, energy))
+    }
+
+    /// Doppler broaden the loaded data to `temperature` K, adding it as a new
+    /// temperature entry in `reactions` and `energy` (and `loaded_temperatures`).
+    ///
+    /// Broadening starts from the 0 K data when loaded, otherwise from the
+    /// lowest loaded temperature, using the SIGMA1 free gas kernel on the
+    /// pointwise data of every reaction (see [`Reaction::doppler_broaden`]).
+    /// Returns the temperature key of the data, which is an existing key when
+    /// the temperature is already loaded.
+    pub fn doppler_broaden(&mut self, temperature: f64) -> Result<String, String> {
+        let loaded: Vec<String> = self.reactions.keys().cloned().collect();
+        if let Some(existing) = loaded
+            .iter()
+            .find(|key| parse_temperature(key) == Some(temperature))
+        {
+            return Ok(existing.clone());
+        }
+        let awr = self.atomic_weight_ratio.ok_or_else(|| {
+            format!(
+                "Nuclide '{}' has no atomic weight ratio, needed for Doppler broadening",
+                self.name.as_deref().unwrap_or("unknown")
+            )
+        })?;
+        let base = broadening_base_temperature(&loaded)?;
+        let base_temperature = parse_temperature(&base).unwrap_or(0.0);
+        if temperature < base_temperature {
+            return Err(format!(
+                "Cannot Doppler broaden to {} K, below the lowest loaded temperature {} K",
+                temperature, base_temperature
+            ));
+        }
+
+        let delta = temperature - base_temperature;
+        let broadened = self.reactions[&base]
+            .iter()
+            .map(|(&mt, reaction)| Ok((mt, reaction.doppler_broaden(awr, delta)?)))
+            .collect::<Result<HashMap<i32, Reaction>, String>>()?;
+        let key = temperature.to_string();
+        self.reactions.insert(key.clone(), broadened);
+        if let Some(energy_map) = self.energy.as_mut() {
+            if let Some(grid) = energy_map.get(&base).cloned() {
+                energy_map.insert(key.clone(), grid);
+            }
+        }
+        if !self.loaded_temperatures.contains(&key) {
+            self.loaded_temperatures.push(key.clone());
+            self.loaded_temperatures.sort();
+        }
+        Ok(key)
+    }
+
+    /// Reconstruct redundant reactions missing from the data by summing their
+    /// components following the ENDF hierarchy (see [`crate::data::SUM_RULES`]),
+    /// e.g. MT 1 from MT 2 and MT 3, MT 4 from the inelastic levels MT 51-91
+    /// and MT 103 from the (n,p) levels MT 600-649.
+    ///
+    /// Components that are missing but redundant themselves are reconstructed
+    /// first. MT 1 is only formed when both MT 2 and MT 3 are available, a
+    /// missing total is reported by [`Nuclide::validate`] instead. Sums are
+    /// formed at every temperature on the nuclide energy grid,
+    /// starting at the lowest component threshold, and the new reactions are
+    /// lin-lin and marked as `derived`. Returns the MTs of all derived reactions.
+    pub fn reconstruct_redundant_reactions(&mut self) -> Vec<i32> {
+        let mut redundant: Vec<i32> = crate::data::SUM_RULES.keys().copied().collect();
+        redundant.sort();
+        let mut added = std::collections::BTreeSet::new();
+        for (temperature, temp_reactions) in self.reactions.iter_mut() {
+            let grid = match self.energy.as_ref().and_then(|e| e.get(temperature)) {
+                Some(grid) if !grid.is_empty() => grid,
+                _ => continue,
+            };
+            for &mt in &redundant {
+                reconstruct_reaction(mt, temp_reactions, grid);
+            }
+            added.extend(temp_reactions.values().filter(|r| r.derived).map(|r| r.mt_number));
+        }
+        added.into_iter().collect()
+    }
+
+    /// Check the loaded data for consistency and report every problem found:
+    /// non-monotonic or repeated energy grid points, negative cross sections,
+    /// threshold indices beyond the energy grid, cross sections whose length
+    /// does not match their energy grid, MT 1 differing from the sum of its
+    /// partials by more than [`crate::validation::DEFAULT_SUM_TOLERANCE`]
+    /// (relative), MT 1 that is missing or cannot be checked because MT 2 or
+    /// MT 3 is unavailable and declared temperatures without data.
+    pub fn validate(&self) -> ValidationReport {
+        self.validate_with_tolerance(crate::validation::DEFAULT_SUM_TOLERANCE)
+    }
+
+    /// As [`Nuclide::validate`] with a relative `tolerance` for comparing MT 1
+    /// with the sum of its partials.
+    pub fn validate_with_tolerance(&self, tolerance: f64) -> ValidationReport {
+        crate::validation::validate_nuclide(self, tolerance)
+    }
+
+    /// Serialize the nuclide to the compact binary format (see
+    /// [`crate::NuclideBinary`]), which loads much faster than JSON.
+    pub fn to_binary(&self) -> Result<Vec<u8>, String> {
+        crate::nuclide_binary::nuclide_to_binary(self)
+    }
+
+    /// Write the nuclide to a file in the binary format. The file can be used
+    /// in place of the JSON file anywhere nuclear data paths are accepted.
+    pub fn export_to_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_binary()?)?;
+        Ok(())
+    }
+
     /// Helper method to automatically load data from config
     fn auto_load_from_config(&mut self, nuclide_name: &str, temperature: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
         // Try to load using the nuclide name and config
@@ -413,6 +622,114 @@ impl Nuclide {
     }
 }
 
+/// MT number of a reaction given as an MT number or reaction name.
+fn reaction_mt<R: Into<ReactionIdentifier>>(reaction: R) -> Result<i32, String> {
+    match reaction.into() {
+        ReactionIdentifier::Mt(mt_num) => Ok(mt_num),
+        ReactionIdentifier::Name(name) => {
+            // Use the REACTION_MT mapping to convert string to MT number
+            crate::data::REACTION_MT.get(name.as_str())
+                .copied()
+                .ok_or_else(|| format!("Unknown reaction name '{}'. Available reactions can be found in REACTION_MT mapping.", name))
+        }
+    }
+}
+
+/// Sum of `reactions` at every point of the nuclide energy grid `grid`, with
+/// the lowest threshold index of the reactions. Reactions whose values do not
+/// line up with the grid from their threshold are interpolated on their own
+/// energy grid. Returns None when no reaction starts inside the grid.
+pub(crate) fn sum_on_grid(reactions: &[&Reaction], grid: &[f64]) -> Option<(usize, Vec<f64>)> {
+    let threshold_idx = reactions
+        .iter()
+        .map(|reaction| reaction.threshold_idx)
+        .filter(|&idx| idx < grid.len())
+        .min()?;
+    let mut sum = vec![0.0; grid.len()];
+    for reaction in reactions {
+        let start = reaction.threshold_idx;
+        if start >= grid.len() {
+            continue;
+        }
+        let on_grid = reaction.cross_section.len() == grid.len() - start;
+        for (i, &energy) in grid.iter().enumerate().skip(start) {
+            sum[i] += if on_grid {
+                reaction.cross_section[i - start]
+            } else if reaction.energy.first().is_some_and(|&e| energy >= e) {
+                reaction.cross_section_on_grid(&reaction.energy, energy)
+            } else {
+                continue;
+            };
+        }
+    }
+    Some((threshold_idx, sum))
+}
+
+/// Add `mt` to `reactions` as the sum of its components on `grid`, first
+/// reconstructing any missing redundant components. MT 1 needs both MT 2 and
+/// MT 3. Returns whether the reaction is available afterwards.
+fn reconstruct_reaction(mt: i32, reactions: &mut HashMap<i32, Reaction>, grid: &[f64]) -> bool {
+    if reactions.contains_key(&mt) {
+        return true;
+    }
+    let rule = match crate::data::SUM_RULES.get(&mt) {
+        Some(rule) => rule,
+        None => return false,
+    };
+    let components: Vec<i32> = rule
+        .iter()
+        .copied()
+        .filter(|&component| reconstruct_reaction(component, reactions, grid))
+        .collect();
+    // Absent channels of the other sums have no cross section, but a total
+    // without elastic or nonelastic would only be a partial sum
+    if mt == 1 && components.len() < rule.len() {
+        return false;
+    }
+    let parts: Vec<&Reaction> = components.iter().map(|component| &reactions[component]).collect();
+    let (threshold_idx, mut cross_section) = match sum_on_grid(&parts, grid) {
+        Some(sum) => sum,
+        None => return false,
+    };
+    cross_section.drain(..threshold_idx);
+    reactions.insert(
+        mt,
+        Reaction {
+            cross_section,
+            threshold_idx,
+            interpolation: vec![crate::interpolation::InterpolationLaw::LinLin.endf_code()],
+            breakpoints: Vec::new(),
+            energy: grid[threshold_idx..].to_vec(),
+            mt_number: mt,
+            derived: true,
+        },
+    );
+    true
+}
+
+/// Every temperature in nuclide JSON data, sorted.
+///
+/// The "temperatures" array is the authoritative list, temperatures that only
+/// appear as keys of the "reactions" or "energy" objects are included too.
+fn json_available_temperatures(json_value: &serde_json::Value) -> Vec<String> {
+    let mut all_temperatures = std::collections::HashSet::new();
+    if let Some(temps_array) = json_value.get("temperatures").and_then(|v| v.as_array()) {
+        for temp_value in temps_array {
+            if let Some(temp_str) = temp_value.as_str() {
+                all_temperatures.insert(temp_str.to_string());
+            }
+        }
+    }
+    for key in ["reactions", "energy"] {
+        if let Some(obj) = json_value.get(key).and_then(|v| v.as_object()) {
+            all_temperatures.extend(obj.keys().cloned());
+        }
+    }
+    let mut available_temps: Vec<String> = all_temperatures.into_iter().collect();
+    available_temps.sort();
+    available_temps
+}
+
 // Internal: parse a nuclide from a JSON value with optional temperature filter.
 // If `temps_filter` is Some, only those temperatures will have reaction/energy data
 // materialized into the returned struct. `available_temperatures` will always list
@@ -428,11 +745,14 @@ fn parse_nuclide_from_json_value(
         atomic_number: None,
         neutron_number: None,
         mass_number: None,
+        metastable: None,
+        atomic_weight_ratio: None,
         library: None,
         energy: None,
         reactions: HashMap::new(),
         fissionable: false,
         available_temperatures: Vec::new(),
+        temperatures_without_data: Vec::new(),
         loaded_temperatures: Vec::new(),
         data_path: None,
     };
@@ -462,79 +782,76 @@ fn parse_nuclide_from_json_value(
         nuclide.mass_number = Some(num as u32);
     }
 
-    if let Some(num) = json_value.get("neutron_number").and_then(|v| v.as_u64()) {
-        nuclide.neutron_number = Some(num as u32);
-    } else if nuclide.mass_number.is_some() && nuclide.atomic_number.is_some() {
-        // Calculate neutron_number = mass_number - atomic_number
-        nuclide.neutron_number =
-            Some(nuclide.mass_number.unwrap() - nuclide.atomic_number.unwrap());
+    if let Some(state) = json_value.get("metastable").and_then(|v| v.as_u64()) {
+        nuclide.metastable = Some(state as u32);
     }
 
-    if let Some(lib) = json_value.get("library").and_then(|v| v.as_str()) {
-        nuclide.library = Some(lib.to_string());
+    if let Some(awr) = json_value.get("atomic_weight_ratio").and_then(|v| v.as_f64()) {
+        if awr <= 0.0 {
+            return Err(format!("atomic_weight_ratio must be positive, got {}", awr).into());
+        }
+        nuclide.atomic_weight_ratio = Some(awr);
     }
 
-    // First, collect ALL available temperatures from multiple locations in the JSON
-    let mut all_temperatures = std::collections::HashSet::new();
-
-    // IMPORTANT: We currently do not support Doppler broadening and therefore ignore
-    // all temperature "0" (0 Kelvin) data present in JSON files. At absolute zero,
-    // nuclear cross sections would require special handling that we don't implement.
-    // This filtering occurs throughout the temperature processing below.
+    // Fill identity fields missing from the JSON from the parsed nuclide name
+    if let Some(id) = nuclide.name.as_deref().and_then(|n| NuclideId::parse(n).ok()) {
+        nuclide.atomic_symbol.get_or_insert_with(|| id.symbol().to_string());
+        nuclide.atomic_number.get_or_insert(id.atomic_number);
+        nuclide.mass_number.get_or_insert(id.mass_number);
+        nuclide.metastable.get_or_insert(id.metastable);
+    }
 
-    // Step 1: Check the dedicated "temperatures" array (this is the source of truth)
-    // The "temperatures" array in the JSON is the authoritative source of all available temperatures
-    if let Some(temps_array) = json_value.get("temperatures").and_then(|v| v.as_array()) {
-        for temp_value in temps_array {
-            if let Some(temp_str) = temp_value.as_str() {
-                // Filter out 0K temperature data - we don't currently support Doppler broadening
-                if temp_str != "0" {
-                    all_temperatures.insert(temp_str.to_string());
-                }
-            }
-        }
+    if let Some(num) = json_value.get("neutron_number").and_then(|v| v.as_u64()) {
+        nuclide.neutron_number = Some(num as u32);
+    } else if let (Some(a), Some(z)) = (nuclide.mass_number, nuclide.atomic_number) {
+        // Calculate neutron_number = mass_number - atomic_number (undefined for
+        // natural element evaluations, which have a mass number of 0)
+        nuclide.neutron_number = if a >= z { Some(a - z) } else { None };
     }
 
-    // Step 2: Also check the reactions object for temperatures
-    if let Some(reactions_obj) = json_value.get("reactions").and_then(|v| v.as_object()) {
-        for temp in reactions_obj.keys() {
-            // Filter out 0K temperature data - we don't currently support Doppler broadening
-            if temp != "0" {
-                all_temperatures.insert(temp.clone());
-            }
-        }
+    if let Some(lib) = json_value.get("library").and_then(|v| v.as_str()) {
+        nuclide.library = Some(lib.to_string());
     }
 
-    // Step 3: Also check energy object for temperatures
-    if let Some(energy_obj) = json_value.get("energy").and_then(|v| v.as_object()) {
-        for temp in energy_obj.keys() {
-            // Filter out 0K temperature data - we don't currently support Doppler broadening
-            if temp != "0" {
-                all_temperatures.insert(temp.clone());
-            }
-        }
+    // Temperatures listed without data in the file, regardless of filtering
+    let has_temperature = |key: &str, temp: &str| {
+        json_value
+            .get(key)
+            .and_then(|v| v.as_object())
+            .is_some_and(|obj| obj.contains_key(temp))
+    };
+    if let Some(temps_array) = json_value.get("temperatures").and_then(|v| v.as_array()) {
+        nuclide.temperatures_without_data = temps_array
+            .iter()
+            .filter_map(|v| v.as_str())
+            .filter(|temp| !has_temperature("reactions", temp) || !has_temperature("energy", temp))
+            .map(str::to_string)
+            .collect();
     }
 
     // Store all available temperatures (regardless of filtering)
-    let mut available_temps: Vec<String> = all_temperatures.iter().cloned().collect();
-    available_temps.sort();
-    nuclide.available_temperatures = available_temps;
+    nuclide.available_temperatures = json_available_temperatures(&json_value);
+
+    // Without a filter every temperature is loaded except 0 K data, which is
+    // only loaded on request as the starting point for Doppler broadening
+    let default_filter: HashSet<String>;
+    let loaded_filter = match temps_filter {
+        Some(filter) if !filter.is_empty() => filter,
+        _ => {
+            default_filter = default_temperatures(&nuclide.available_temperatures)
+                .into_iter()
+                .collect();
+            &default_filter
+        }
+    };
 
     // Check if we have the format with "reactions" field
     if let Some(reactions_obj) = json_value.get("reactions").and_then(|v| v.as_object()) {
         for (temp, mt_reactions) in reactions_obj {
-            // Filter out 0K temperature data - we don't currently support Doppler broadening
-            if temp == "0" {
+            if !loaded_filter.contains(temp) {
                 continue;
             }
 
-            // Skip temperatures not in the filter if a filter is provided and non-empty
-            if let Some(filter) = temps_filter {
-                if !filter.is_empty() && !filter.contains(temp) {
-                    continue;
-                }
-            }
-
             let mut temp_reactions: HashMap<i32, Reaction> = HashMap::new();
 
             // Process all MT reactions for this temperature
@@ -545,8 +862,10 @@ fn parse_nuclide_from_json_value(
                             cross_section: Vec::new(),
                             threshold_idx: 0,
                             interpolation: Vec::new(),
+                            breakpoints: Vec::new(),
                             energy: Vec::new(),
                             mt_number: 0,
+                            derived: false,
                         };
 
                         // Get cross section (might be named "xs" in old format)
@@ -577,6 +896,16 @@ fn parse_nuclide_from_json_value(
                             }
                         }
 
+                        // Get interpolation breakpoints (ENDF NBT values)
+                        if let Some(breakpoints) =
+                            reaction_obj.get("breakpoints").and_then(|v| v.as_array())
+                        {
+                            reaction.breakpoints = breakpoints
+                                .iter()
+                                .filter_map(|v| v.as_u64().map(|i| i as usize))
+                                .collect();
+                        }
+
                         // Get energy (some reactions may have their own energy grid)
                         if let Some(energy) = reaction_obj.get("energy") {
                             if let Some(energy_arr) = energy.as_array() {
@@ -599,6 +928,9 @@ fn parse_nuclide_from_json_value(
 
                         if let Ok(mt_int) = mt.parse::<i32>() {
                             reaction.mt_number = mt_int;
+                            reaction
+                                .check_interpolation()
+                                .map_err(|e| format!("Temperature {}: {}", temp, e))?;
                             temp_reactions.insert(mt_int, reaction);
                         }
                     }
@@ -617,18 +949,10 @@ fn parse_nuclide_from_json_value(
         let mut energy_map = HashMap::new();
 
         for (temp, energy_arr) in energy_obj {
-            // Filter out 0K temperature data - we don't currently support Doppler broadening
-            if temp == "0" {
+            if !loaded_filter.contains(temp) {
                 continue;
             }
 
-            // Skip temperatures not in the filter if a filter is provided and non-empty
-            if let Some(filter) = temps_filter {
-                if !filter.is_empty() && !filter.contains(temp) {
-                    continue;
-                }
-            }
-
             if let Some(energy_values) = energy_arr.as_array() {
                 let energy_vec: Vec<f64> =
                     energy_values.iter().filter_map(|v| v.as_f64()).collect();
@@ -683,7 +1007,9 @@ fn parse_nuclide_from_json_value(
             temps
         )
         .into());
-    } else {
+    } else if temps_filter.is_none_or(|filter| filter.is_empty()) {
+        // No data in the file at all. When a filter excluded every temperature
+        // the file's temperatures are still available to load later.
         nuclide.available_temperatures.clear();
     }
 
@@ -721,19 +1047,29 @@ fn parse_nuclide_from_json_value(
         }
     }
 
-    // Fallback: derive name if still None (e.g., from atomic_symbol + mass_number)
+    // Fallback: derive name if still None (e.g., from atomic_symbol + mass_number,
+    // with the metastable state when present)
     if nuclide.name.is_none() {
-        if let (Some(symbol), Some(mass)) = (&nuclide.atomic_symbol, nuclide.mass_number) {
+        let metastable = nuclide.metastable.unwrap_or(0);
+        let id = match (nuclide.atomic_number, nuclide.mass_number) {
+            (Some(z), Some(a)) => NuclideId::new(z, a, metastable).ok(),
+            _ => None,
+        };
+        if let Some(id) = id {
+            nuclide.name = Some(id.name());
+        } else if let (Some(symbol), Some(mass)) = (&nuclide.atomic_symbol, nuclide.mass_number) {
             nuclide.name = Some(format!("{}{}", symbol, mass));
         }
     }
 
+    // Fill in summed reactions (e.g. MT 1, 3, 101) the library does not provide
+    nuclide.reconstruct_redundant_reactions();
+
     // Determine fissionable status now that reactions are loaded
-    let fission_mt_list = [18, 19, 20, 21, 38];
     if nuclide
         .reactions
         .values()
-        .any(|temp_reactions| temp_reactions.keys().any(|mt| fission_mt_list.contains(mt)))
+        .any(|temp_reactions| temp_reactions.keys().any(|mt| FISSION_MTS.contains(mt)))
     {
         nuclide.fissionable = true;
     }
@@ -751,7 +1087,8 @@ fn parse_nuclide_from_json_value(
 
 /// Read a single nuclide either from an explicit JSON file path, or if the input is not a file path,
 /// treat the argument as a nuclide name and look up the path in the global CONFIG.cross_sections map.
-/// Optional temperature filtering is supported. URLs are automatically downloaded and cached.
+/// Optional temperature filtering is supported, without it every temperature except 0 K data
+/// is loaded. URLs are automatically downloaded and cached.
 pub fn read_nuclide_from_json<P: AsRef<Path>>(
     path_or_name: P,
     temps: Option<&std::collections::HashSet<String>>,
@@ -771,6 +1108,16 @@ pub fn read_nuclide_from_json_with_name<P: AsRef<Path>>(
     path_or_name: P,
     temps: Option<&std::collections::HashSet<String>>,
     nuclide_name_hint: Option<&str>,
+) -> Result<Nuclide, Box<dyn std::error::Error>> {
+    read_nuclide_selecting(path_or_name, &|_| temps.cloned().unwrap_or_default(), nuclide_name_hint)
+}
+
+/// Read a single nuclide like [`read_nuclide_from_json_with_name`], keeping
+/// the temperatures chosen by `select` from those available in the file.
+pub(crate) fn read_nuclide_selecting<P: AsRef<Path>>(
+    path_or_name: P,
+    select: &TemperatureSelector,
+    nuclide_name_hint: Option<&str>,
 ) -> Result<Nuclide, Box<dyn std::error::Error>> {
     // Load the JSON file
     let candidate_ref = path_or_name.as_ref();
@@ -782,20 +1129,20 @@ pub fn read_nuclide_from_json_with_name<P: AsRef<Path>>(
     } else if crate::url_cache::is_url(&candidate_str) {
         // It's a URL, download and cache it
         if let Some(name) = nuclide_name_hint {
-            crate::url_cache::resolve_path_or_url(&candidate_str, name)?
+            crate::url_cache::resolve_path_or_url(&candidate_str, &canonical_nuclide_name(name))?
         } else {
             return Err("Direct URL loading without nuclide name not yet supported. Use config approach instead.".into());
         }
     } else if crate::url_cache::is_keyword(&candidate_str) {
         // It's a keyword: treat as data source for the nuclide name
         let nuclide_name = nuclide_name_hint.unwrap_or(candidate_str.as_ref());
-        crate::url_cache::resolve_path_or_url(&candidate_str, nuclide_name)?
+        crate::url_cache::resolve_path_or_url(&candidate_str, &canonical_nuclide_name(nuclide_name))?
     } else {
         // Treat as nuclide name, look up in config
         let cfg = crate::config::CONFIG.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
         let path_or_url = cfg
             .cross_sections
-            .get(candidate_str.as_ref())
+            .get(&canonical_nuclide_name(&candidate_str))
             .ok_or_else(|| {
                 format!(
                     "Input '{}' is neither an existing file nor a key in Config cross_sections",
@@ -803,17 +1150,41 @@ pub fn read_nuclide_from_json_with_name<P: AsRef<Path>>(
                 )
             })?;
         // The config value might be a URL or local path
-        crate::url_cache::resolve_path_or_url(path_or_url, candidate_str.as_ref())?
+        crate::url_cache::resolve_path_or_url(path_or_url, &canonical_nuclide_name(&candidate_str))?
     };
 
-    let file = File::open(&resolved_path)?;
-    let reader = BufReader::new(file);
-    let json_value: serde_json::Value = serde_json::from_reader(reader)?;
-
-    // Use the filtering version of parse_nuclide_from_json_value
-    let mut nuclide = parse_nuclide_from_json_value(json_value, temps)?;
-    nuclide.data_path = Some(resolved_path.to_string_lossy().to_string());
+    read_nuclide_file(&resolved_path, select)
+}
 
+/// Temperatures of a nuclide file to load, chosen from every temperature
+/// available in the file. An empty set loads all of them except 0 K data
+/// (see [`crate::temperature::default_temperatures`]).
+pub(crate) type TemperatureSelector<'a> = dyn Fn(&[String]) -> HashSet<String> + 'a;
+
+/// Read a nuclide data file, JSON, binary or ACE (recognized from its first
+/// bytes, or the `.ace` extension), keeping only the temperatures chosen by
+/// `select` from those available in the file. The file is read once and
+/// `available_temperatures` always lists every temperature in it.
+fn read_nuclide_file(
+    path: &Path,
+    select: &TemperatureSelector,
+) -> Result<Nuclide, Box<dyn std::error::Error>> {
+    if crate::nuclide_binary::is_binary_nuclide_file(path) {
+        let binary = crate::nuclide_binary::NuclideBinary::open(path)?;
+        let temps = select(binary.available_temperatures());
+        let mut nuclide = binary.to_nuclide(Some(&temps), None);
+        nuclide.data_path = Some(path.to_string_lossy().to_string());
+        return Ok(nuclide);
+    }
+    if crate::ace::is_ace_file(path) {
+        return crate::ace::read_nuclide_from_ace_selecting(path, select);
+    }
+    // Parsing from a byte buffer is much faster than from a reader
+    let bytes = std::fs::read(path)?;
+    let json_value: serde_json::Value = serde_json::from_slice(&bytes)?;
+    let temps = select(&json_available_temperatures(&json_value));
+    let mut nuclide = parse_nuclide_from_json_value(json_value, Some(&temps))?;
+    nuclide.data_path = Some(path.to_string_lossy().to_string());
     Ok(nuclide)
 }
 
@@ -842,21 +1213,39 @@ pub fn read_nuclide_from_json_str(
 /// - `temperatures_to_include`: `None` or `Some(empty)` => load all temperatures.
 /// - `Some(nonempty)` => ensure those temperatures are loaded; if previously loaded with fewer temps, reload & prune to the union.
 ///
-/// Implementation note: This function performs two passes on the JSON data when filtering:
-/// - First pass with no filter to determine all available temperatures
-/// - Second pass with the filter to load only the requested temperatures
+/// Files in the binary format (see [`crate::NuclideBinary`]) are read the same
+/// way, reading only the arrays of the requested temperatures.
 pub fn get_or_load_nuclide(
     nuclide_name: &str,
     json_path_map: &HashMap<String, String>,
-    temperatures_to_include: Option<&std::collections::HashSet<String>>,
+    temperatures_to_include: Option<&HashSet<String>>,
 ) -> Result<Arc<Nuclide>, Box<dyn std::error::Error>> {
-    use std::collections::HashSet;
-    let requested: HashSet<String> = temperatures_to_include
-        .map(|s| s.iter().cloned().collect())
-        .unwrap_or_else(HashSet::new);
+    let requested: HashSet<String> = temperatures_to_include.cloned().unwrap_or_default();
+    get_or_load_nuclide_selecting(nuclide_name, json_path_map, &|_| requested.clone())
+}
+
+/// Read (or fetch from cache) a nuclide like [`get_or_load_nuclide`], with the
+/// temperatures to include chosen by `select` from those available in the
+/// data. The selection is made from the cached nuclide when there is one, or
+/// while reading the file, so the file is read at most once.
+pub(crate) fn get_or_load_nuclide_selecting(
+    nuclide_name: &str,
+    json_path_map: &HashMap<String, String>,
+    select: &TemperatureSelector,
+) -> Result<Arc<Nuclide>, Box<dyn std::error::Error>> {
+    // Any common name form (e.g. "Li-6", "3006") shares the canonical name's cache entry
+    let canonical_name = canonical_nuclide_name(nuclide_name);
 
     // Determine data source for cache key
-    let mut path_or_url = json_path_map.get(nuclide_name).cloned();
+    let mut path_or_url = json_path_map
+        .get(nuclide_name)
+        .or_else(|| {
+            json_path_map
+                .iter()
+                .find(|(name, _)| canonical_nuclide_name(name) == canonical_name)
+                .map(|(_, path)| path)
+        })
+        .cloned();
     if path_or_url.is_none() && crate::url_cache::is_keyword(nuclide_name) {
         let mut cfg = crate::config::CONFIG.lock().unwrap();
         cfg.set_cross_section(nuclide_name, Some(nuclide_name));
@@ -870,7 +1259,7 @@ pub fn get_or_load_nuclide(
     })?;
     
     // Resolve URL/keyword to actual path first to create consistent cache keys
-    let resolved_path = crate::url_cache::resolve_path_or_url(&path_or_url, nuclide_name)?;
+    let resolved_path = crate::url_cache::resolve_path_or_url(&path_or_url, &canonical_name)?;
     
     // Create cache key using the resolved path for consistency
     // This ensures "tendl-21" and the actual downloaded path use the same cache entry
@@ -881,59 +1270,41 @@ pub fn get_or_load_nuclide(
             resolved_path.to_string_lossy().to_string()
         }
     };
-    let cache_key = format!("{}@{}", nuclide_name, normalized_source);
+    let cache_key = format!("{}@{}", canonical_name, normalized_source);
 
-    // Fast path: cache hit with sufficient temps
-    {
+    // Fast path: cache hit with sufficient temps. Temperatures not in the data
+    // cannot be loaded, so they do not force a reload.
+    let existing = {
         let cache = match GLOBAL_NUCLIDE_CACHE.lock() {
             Ok(cache) => cache,
             Err(poisoned) => poisoned.into_inner()
         };
-        if let Some(existing) = cache.get(&cache_key) {
-            if requested.is_empty()
-                || requested.is_subset(&existing.loaded_temperatures.iter().cloned().collect())
-            {
-                return Ok(Arc::clone(existing));
-            }
+        cache.get(&cache_key).cloned()
+    };
+    if let Some(existing) = &existing {
+        let requested = select(&existing.available_temperatures);
+        if requested.is_empty()
+            || requested
+                .iter()
+                .filter(|t| existing.available_temperatures.contains(t))
+                .all(|t| existing.loaded_temperatures.contains(t))
+        {
+            return Ok(Arc::clone(existing));
         }
     }
-    // Determine union (existing loaded + requested)
-    let mut union_set = requested.clone();
-    {
-        let cache = match GLOBAL_NUCLIDE_CACHE.lock() {
-            Ok(cache) => cache,
-            Err(poisoned) => poisoned.into_inner()
-        };
-        if let Some(existing) = cache.get(&cache_key) {
-            for t in &existing.loaded_temperatures {
-                union_set.insert(t.clone());
+
+    // A single filtered read keeping the union of the selected and already
+    // loaded temperatures, available_temperatures still lists every temperature in the file
+    let union_select = |available: &[String]| {
+        let mut union_set = select(available);
+        if let Some(existing) = &existing {
+            if !union_set.is_empty() {
+                union_set.extend(existing.loaded_temperatures.iter().cloned());
             }
         }
-    }
-    // Load directly with temperature filtering
-    // (resolved_path already determined above for cache key)
-
-    // Load the JSON file once
-    let file = File::open(&resolved_path)?;
-    let reader = BufReader::new(file);
-    let json_value: serde_json::Value = serde_json::from_reader(reader)?;
-
-    // Parse with temperature filter if needed
-    let filter_option = if union_set.is_empty() {
-        None
-    } else {
-        Some(&union_set)
+        union_set
     };
-
-    // First, do a parse with no filtering to get all available temperatures
-    let all_temps_nuclide = parse_nuclide_from_json_value(json_value.clone(), None)?;
-
-    // Now parse with the filter to get the loaded data
-    let mut nuclide = parse_nuclide_from_json_value(json_value, filter_option)?;
-    nuclide.data_path = Some(resolved_path.to_string_lossy().to_string());
-
-    // Copy the available_temperatures from the unfiltered parse
-    nuclide.available_temperatures = all_temps_nuclide.available_temperatures;
+    let nuclide = read_nuclide_file(&resolved_path, &union_select)?;
 
     // Print loading info
     let name_disp = nuclide.name.as_deref().unwrap_or(nuclide_name);
@@ -979,29 +1350,13 @@ pub fn load_nuclide_for_python(
         cfg.set_cross_section(identifier, Some(identifier));
     }
 
-    // Load without temperature filtering first to get all available temperatures
-    let full_nuclide = if path_or_keyword.is_some() && nuclide_name.is_some() {
+    // Filtered reads keep the full available_temperatures, so one read is enough
+    if path_or_keyword.is_some() && nuclide_name.is_some() {
         // We have both path and name - use the version with name hint
-        read_nuclide_from_json_with_name(identifier, None, nuclide_name)?
-    } else {
-        read_nuclide_from_json(identifier, None)?
-    };
-
-    // Now load with temperature filtering if specified
-    let mut filtered_nuclide = if temperatures.is_some() {
-        if path_or_keyword.is_some() && nuclide_name.is_some() {
-            read_nuclide_from_json_with_name(identifier, temperatures, nuclide_name)?
-        } else {
-            read_nuclide_from_json(identifier, temperatures)?
-        }
+        read_nuclide_from_json_with_name(identifier, temperatures, nuclide_name)
     } else {
-        full_nuclide.clone()
-    };
-
-    // Always preserve the full available_temperatures from the unfiltered load
-    filtered_nuclide.available_temperatures = full_nuclide.available_temperatures;
-    
-    Ok(filtered_nuclide)
+        read_nuclide_from_json(identifier, temperatures)
+    }
 }
 
 /// Load a nuclide from a path or keyword for the standalone Python function
@@ -1021,6 +1376,37 @@ pub fn load_nuclide_from_path_or_keyword(
 }
 
 mod tests {
+    #[test]
+    fn test_get_or_load_nuclide_selecting_reads_once() {
+        use std::cell::Cell;
+        use std::collections::{HashMap, HashSet};
+        // A copy unique to this run starts without a cache entry
+        let path = std::env::temp_dir().join(format!(
+            "materials_for_mc_test_{}_select_Be9.json",
+            std::process::id()
+        ));
+        std::fs::copy("tests/Be9.json", &path).unwrap();
+        let json_map = HashMap::from([("Be9".to_string(), path.to_string_lossy().to_string())]);
+
+        let calls = Cell::new(0);
+        let select = |available: &[String]| {
+            calls.set(calls.get() + 1);
+            assert_eq!(available, ["294".to_string(), "300".to_string()]);
+            available.iter().cloned().collect::<HashSet<String>>()
+        };
+        let first = super::get_or_load_nuclide_selecting("Be9", &json_map, &select).unwrap();
+        assert_eq!(calls.get(), 1);
+        let mut loaded = first.loaded_temperatures.clone();
+        loaded.sort();
+        assert_eq!(loaded, vec!["294", "300"]);
+
+        // The selection is made from the cached nuclide without reading again
+        let second = super::get_or_load_nuclide_selecting("Be9", &json_map, &select).unwrap();
+        assert_eq!(calls.get(), 2);
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_get_or_load_nuclide_uses_cache() {
         use std::collections::HashMap;
@@ -1146,6 +1532,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_or_load_nuclide_shares_cache_across_name_forms() {
+        let json_map =
+            std::collections::HashMap::from([("li7".to_string(), "tests/Li7.json".to_string())]);
+        let hyphenated = super::get_or_load_nuclide("Li-7", &json_map, None).unwrap();
+        let zaid = super::get_or_load_nuclide("3007.80c", &json_map, None).unwrap();
+        let gnds = super::get_or_load_nuclide("Li7", &json_map, None).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&hyphenated, &zaid));
+        assert!(std::sync::Arc::ptr_eq(&hyphenated, &gnds));
+    }
+
+    #[test]
+    fn test_identity_from_name_for_natural_and_metastable() {
+        let carbon = super::read_nuclide_from_json_str(r#"{"name": "C0"}"#).unwrap();
+        assert_eq!(carbon.atomic_symbol.as_deref(), Some("C"));
+        assert_eq!(carbon.atomic_number, Some(6));
+        assert_eq!(carbon.mass_number, Some(0));
+        assert_eq!(carbon.neutron_number, None);
+        assert!(carbon.nuclide_id().unwrap().is_natural());
+
+        let americium = super::read_nuclide_from_json_str(r#"{"name": "Am242_m1"}"#).unwrap();
+        assert_eq!(americium.mass_number, Some(242));
+        assert_eq!(americium.neutron_number, Some(147));
+        assert_eq!(americium.nuclide_id().unwrap().metastable, 1);
+
+        let li6 = super::read_nuclide_from_json("tests/Li6.json", None).unwrap();
+        assert_eq!(li6.nuclide_id().unwrap().name(), "Li6");
+    }
+
     #[test]
     fn test_reaction_mts_li6() {
         // Load Li6 nuclide from test JSON
@@ -1326,6 +1741,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filtered_read_keeps_available_temperatures() {
+        let only_300: std::collections::HashSet<String> = ["300".to_string()].into_iter().collect();
+        let be9 = super::read_nuclide_from_json("tests/Be9.json", Some(&only_300)).unwrap();
+        assert_eq!(be9.available_temperatures, vec!["294", "300"]);
+        assert_eq!(be9.loaded_temperatures, vec!["300"]);
+
+        // Also when the filter matches none of the file's temperatures
+        let none: std::collections::HashSet<String> = ["1200".to_string()].into_iter().collect();
+        let be9 = super::read_nuclide_from_json("tests/Be9.json", Some(&none)).unwrap();
+        assert_eq!(be9.available_temperatures, vec!["294", "300"]);
+        assert!(be9.loaded_temperatures.is_empty());
+    }
+
     #[test]
     fn test_clear_nuclide_cache() {
         // Insert a test nuclide into the cache
@@ -1487,6 +1916,103 @@ mod tests {
                "Error should list the loaded temperatures: {}", error_msg);
     }
 
+    #[test]
+    fn test_microscopic_cross_section_at_temperature() {
+        use crate::temperature::TemperatureMethod;
+        let mut nuclide = super::read_nuclide_from_json("tests/Be9.json", None)
+            .expect("Failed to load Be9.json");
+        let (xs_294, energy_294) = nuclide.microscopic_cross_section(2, Some("294")).unwrap();
+        let (xs_300, energy_300) = nuclide.microscopic_cross_section(2, Some("300")).unwrap();
+
+        let nearest = nuclide
+            .microscopic_cross_section_at_temperature(2, 298.0, TemperatureMethod::Nearest)
+            .unwrap();
+        assert_eq!(nearest, (xs_300.clone(), energy_300.clone()));
+        let below = nuclide
+            .microscopic_cross_section_at_temperature("(n,elastic)", 20.0, TemperatureMethod::Interpolation)
+            .unwrap();
+        assert_eq!(below, (xs_294.clone(), energy_294.clone()));
+
+        let (xs, energy) = nuclide
+            .microscopic_cross_section_at_temperature(2, 296.0, TemperatureMethod::Interpolation)
+            .unwrap();
+        assert_eq!(xs.len(), energy.len());
+        let e = 2.0e6;
+        let low = crate::utilities::interpolate_linear(&energy_294, &xs_294, e);
+        let high = crate::utilities::interpolate_linear(&energy_300, &xs_300, e);
+        let value = crate::utilities::interpolate_linear(&energy, &xs, e);
+        assert!((value - (low + (high - low) / 3.0)).abs() <= 1e-9 * value.abs());
+
+        // Tabulated stochastic cross sections are the expected, interpolated values
+        let stochastic = nuclide
+            .microscopic_cross_section_at_temperature(2, 296.0, TemperatureMethod::Stochastic)
+            .unwrap();
+        assert_eq!(stochastic, (xs, energy));
+    }
+
+    #[test]
+    fn test_zero_kelvin_data_only_loaded_for_broadening() {
+        use crate::temperature::TemperatureMethod;
+        let json = r#"{"name": "Li6", "atomic_weight_ratio": 5.9634, "temperatures": ["0", "294"],
+            "energy": {"0": [1.0, 2.0], "294": [1.0, 2.0]},
+            "reactions": {"0": {"2": {"cross_section": [4.0, 4.0], "threshold_idx": 0}},
+                          "294": {"2": {"cross_section": [3.0, 3.0], "threshold_idx": 0}}}}"#;
+        let mut li6 = super::read_nuclide_from_json_str(json).unwrap();
+        assert_eq!(li6.available_temperatures, vec!["0", "294"]);
+        assert_eq!(li6.loaded_temperatures, vec!["294"]);
+        assert_eq!(li6.microscopic_cross_section(2, None).unwrap().0, vec![3.0, 3.0]);
+        for method in [TemperatureMethod::Nearest, TemperatureMethod::Interpolation] {
+            let (xs, _) = li6.microscopic_cross_section_at_temperature(2, 100.0, method).unwrap();
+            assert_eq!(xs, vec![3.0, 3.0], "{}", method);
+            assert_eq!(method.required_temperatures(&li6.available_temperatures, 100.0).unwrap(), vec!["294"]);
+        }
+        assert_eq!(
+            TemperatureMethod::Broadening
+                .required_temperatures(&li6.available_temperatures, 600.0)
+                .unwrap(),
+            vec!["0"]
+        );
+
+        let zero = std::collections::HashSet::from(["0".to_string()]);
+        let path = std::env::temp_dir().join(format!("materials_for_mc_test_{}_zero_Li6.json", std::process::id()));
+        std::fs::write(&path, json).unwrap();
+        let li6 = super::read_nuclide_from_json(&path, Some(&zero)).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(li6.loaded_temperatures, vec!["0"]);
+    }
+
+    #[test]
+    fn test_doppler_broaden_adds_temperature() {
+        use crate::temperature::TemperatureMethod;
+        let mut nuclide = super::read_nuclide_from_json("tests/Be9.json", None)
+            .expect("Failed to load Be9.json");
+        // Broadening 294 K data by 6 K reproduces the 300 K evaluation
+        let mut from_294 = nuclide.clone();
+        from_294.reactions.remove("300");
+        let key = from_294.doppler_broaden(300.0).unwrap();
+        assert_eq!(key, "300");
+        let (xs_300, energy_300) = nuclide.microscopic_cross_section(2, Some("300")).unwrap();
+        let (xs, energy) = from_294.microscopic_cross_section(2, Some("300")).unwrap();
+        for e in [1e-4, 0.0253, 1.0, 1e3, 1e6] {
+            let expected = crate::utilities::interpolate_linear(&energy_300, &xs_300, e);
+            let value = crate::utilities::interpolate_linear(&energy, &xs, e);
+            assert!((value - expected).abs() < 0.01 * expected, "E={} got {} expected {}", e, value, expected);
+        }
+
+        let key = nuclide.doppler_broaden(1200.0).unwrap();
+        assert_eq!(key, "1200");
+        assert!(nuclide.loaded_temperatures.contains(&key));
+        assert_eq!(nuclide.energy_grid("1200"), nuclide.energy_grid("294"));
+        assert_eq!(nuclide.reactions["1200"].len(), nuclide.reactions["294"].len());
+        assert_eq!(nuclide.doppler_broaden(300.0).unwrap(), "300");
+        assert!(nuclide.doppler_broaden(10.0).is_err());
+
+        let (xs, _) = nuclide
+            .microscopic_cross_section_at_temperature(2, 900.0, TemperatureMethod::Broadening)
+            .unwrap();
+        assert_eq!(xs, nuclide.microscopic_cross_section(2, Some("900")).unwrap().0);
+    }
+
     #[test]
     fn test_microscopic_cross_section_invalid_temperature() {
         let mut nuclide = super::read_nuclide_from_json("tests/Be9.json", None)
@@ -1615,11 +2141,14 @@ mod tests {
             atomic_number: None,
             neutron_number: None,
             mass_number: None,
+            metastable: None,
+            atomic_weight_ratio: None,
             library: None,
             energy: None,
             reactions: std::collections::HashMap::new(),
             fissionable: false,
             available_temperatures: Vec::new(),
+            temperatures_without_data: Vec::new(),
             loaded_temperatures: Vec::new(),
             data_path: None,
         };
@@ -1707,11 +2236,14 @@ mod tests {
             atomic_number: None,
             neutron_number: None,
             mass_number: None,
+            metastable: None,
+            atomic_weight_ratio: None,
             library: None,
             energy: None,
             reactions: std::collections::HashMap::new(),
             fissionable: false,
             available_temperatures: Vec::new(),
+            temperatures_without_data: Vec::new(),
             loaded_temperatures: Vec::new(),
             data_path: None,
         };
@@ -1773,11 +2305,14 @@ mod tests {
             atomic_number: None,
             neutron_number: None,
             mass_number: None,
+            metastable: None,
+            atomic_weight_ratio: None,
             library: None,
             energy: None,
             reactions: std::collections::HashMap::new(),
             fissionable: false,
             available_temperatures: Vec::new(),
+            temperatures_without_data: Vec::new(),
             loaded_temperatures: Vec::new(),
             data_path: None,
         };
@@ -1980,4 +2515,100 @@ mod tests {
                                   xs_li6_1.iter().zip(&xs_li7).any(|(a, b)| (a - b).abs() > 1e-10);
         assert!(li6_vs_li7_different, "Li6 and Li7 should have different data");
     }
+
+    #[test]
+    fn test_atomic_weight_ratio_and_metastable_from_json() {
+        let li6 = super::read_nuclide_from_json("tests/Li6.json", None)
+            .expect("Failed to load Li6");
+        assert_eq!(li6.atomic_weight_ratio, Some(5.9634));
+        assert_eq!(li6.metastable, Some(0));
+        let mass = li6.atomic_mass().unwrap();
+        assert!((mass - 5.9634 * crate::data::NEUTRON_MASS).abs() < 1e-12);
+        assert!((mass - 6.015).abs() < 0.01, "Li6 mass from AWR was {}", mass);
+
+        let json = r#"{"atomic_number": 95, "mass_number": 242, "metastable": 1,
+            "atomic_weight_ratio": 239.9, "temperatures": [], "reactions": {}}"#;
+        let am = super::read_nuclide_from_json_str(json).unwrap();
+        assert_eq!(am.metastable, Some(1));
+        assert_eq!(am.name.as_deref(), Some("Am242_m1"));
+
+        let bad = r#"{"name": "Li6", "atomic_weight_ratio": -1.0, "reactions": {}}"#;
+        assert!(super::read_nuclide_from_json_str(bad).is_err());
+    }
+
+    #[test]
+    fn test_reconstruct_redundant_reactions_matches_library_sums() {
+        let original = super::read_nuclide_from_json("tests/Li6.json", None).unwrap();
+        assert_eq!(original.clone().reconstruct_redundant_reactions(), Vec::<i32>::new());
+        assert!(original.reactions["294"].values().all(|r| !r.derived));
+
+        let mut li6 = original.clone();
+        let reactions = li6.reactions.get_mut("294").unwrap();
+        for mt in [1, 3, 4, 27, 101] {
+            reactions.remove(&mt);
+        }
+        assert_eq!(li6.reconstruct_redundant_reactions(), vec![1, 3, 4, 27, 101]);
+        for mt in [1, 3, 4, 27, 101] {
+            let expected = &original.reactions["294"][&mt];
+            let rebuilt = &li6.reactions["294"][&mt];
+            assert!(rebuilt.derived);
+            let offset = expected.threshold_idx - rebuilt.threshold_idx;
+            assert!(rebuilt.cross_section[..offset].iter().all(|&xs| xs == 0.0));
+            for (a, b) in rebuilt.cross_section[offset..].iter().zip(&expected.cross_section) {
+                assert!((a - b).abs() <= 1e-9 * b.abs(), "MT {}: {} vs {}", mt, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_total_from_partial_reactions() {
+        let json = r#"{"name": "Li6", "temperatures": ["294"], "energy": {"294": [1.0, 2.0, 3.0, 4.0]},
+            "reactions": {"294": {
+                "2": {"cross_section": [1.0, 1.0, 1.0, 1.0], "threshold_idx": 0},
+                "102": {"cross_section": [4.0, 3.0, 2.0, 1.0], "threshold_idx": 0},
+                "51": {"cross_section": [0.5, 0.5], "threshold_idx": 2},
+                "600": {"cross_section": [0.25], "threshold_idx": 3}}}}"#;
+        let mut li6 = super::read_nuclide_from_json_str(json).unwrap();
+        let (total, energy) = li6.microscopic_cross_section(1, Some("294")).unwrap();
+        assert_eq!(energy, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(total, vec![5.0, 4.0, 3.5, 2.75]);
+        let reactions = &li6.reactions["294"];
+        assert!(reactions[&1].derived && reactions[&3].derived && !reactions[&2].derived);
+        assert_eq!(reactions[&4].threshold_idx, 2);
+        assert_eq!(reactions[&103].cross_section, vec![0.25]);
+        assert_eq!(reactions[&101].cross_section, vec![4.0, 3.0, 2.0, 1.25]);
+        assert!(!reactions.contains_key(&16) && !reactions.contains_key(&18));
+    }
+
+    #[test]
+    fn test_total_needs_elastic_and_nonelastic() {
+        let json = r#"{"name": "Li6", "temperatures": ["294"], "energy": {"294": [1.0, 2.0]},
+            "reactions": {"294": {"2": {"cross_section": [1.0, 1.0], "threshold_idx": 0}}}}"#;
+        let mut li6 = super::read_nuclide_from_json_str(json).unwrap();
+        assert!(!li6.reactions["294"].contains_key(&1));
+        assert_eq!(li6.reconstruct_redundant_reactions(), Vec::<i32>::new());
+
+        let json = json.replace(r#""2": {"#, r#""102": {"#);
+        let mut li6 = super::read_nuclide_from_json_str(&json).unwrap();
+        assert_eq!(li6.reconstruct_redundant_reactions(), vec![3, 27, 101]);
+        assert!(!li6.reactions["294"].contains_key(&1));
+    }
+
+    #[test]
+    fn test_interpolation_regions_from_json() {
+        let json = r#"{"name": "Li6", "temperatures": ["294"], "energy": {"294": [1.0, 2.0, 4.0]},
+            "reactions": {"294": {"102": {"cross_section": [3.0, 2.0, 1.0], "threshold_idx": 0,
+                "interpolation": [1, 2], "breakpoints": [2, 3]}}}}"#;
+        let li6 = super::read_nuclide_from_json_str(json).unwrap();
+        let reaction = &li6.reactions["294"][&102];
+        assert_eq!(reaction.breakpoints, vec![2, 3]);
+        assert_eq!(reaction.cross_section_at(1.5), Some(3.0));
+        assert_eq!(reaction.cross_section_at(3.0), Some(1.5));
+
+        let bad = json.replace("[1, 2]", "[1, 9]");
+        let err = super::read_nuclide_from_json_str(&bad).unwrap_err().to_string();
+        assert!(err.contains("MT 102"), "{}", err);
+        let bad = json.replace("[2, 3]", "[2]");
+        assert!(super::read_nuclide_from_json_str(&bad).is_err());
+    }
 }
@@ -0,0 +1,539 @@
+// Provides a compact binary format for nuclide data with memory-mapped access to the arrays
+//
+// Layout (all integers and floats little-endian):
+//
+//   offset 0   magic b"MFMCNUC\0"
+//   offset 8   u32 format version
+//   offset 12  u32 reserved, zero
+//   offset 16  u64 header length in bytes
+//   offset 24  header: UTF-8 JSON with the nuclide metadata and an index of
+//              every temperature and MT, giving the position of each array
+//              then zero padding up to a multiple of 8 bytes
+//   data       f64 arrays (energy grids, cross sections), each 8-byte aligned
+//
+// Reading the header is enough to know what the file holds, the arrays of the
+// selected temperatures and MTs are then read straight from the mapped file.
+use crate::nuclide::Nuclide;
+use crate::reaction::Reaction;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+
+/// First bytes of every binary nuclide file.
+pub const BINARY_MAGIC: &[u8; 8] = b"MFMCNUC\0";
+
+/// Version of the binary layout written by [`Nuclide::to_binary`].
+pub const BINARY_VERSION: u32 = 1;
+
+/// Bytes before the header: magic, version, reserved word and header length.
+const PREAMBLE_LEN: usize = 24;
+
+/// Position of an array in the data section, in f64 values.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ArrayRef {
+    offset: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReactionIndex {
+    threshold_idx: usize,
+    #[serde(default)]
+    interpolation: Vec<i32>,
+    #[serde(default)]
+    breakpoints: Vec<usize>,
+    #[serde(default)]
+    derived: bool,
+    cross_section: ArrayRef,
+    /// Only stored when the reaction grid is not the nuclide grid from threshold_idx.
+    #[serde(default)]
+    energy: Option<ArrayRef>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TemperatureIndex {
+    energy: Option<ArrayRef>,
+    reactions: BTreeMap<i32, ReactionIndex>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Header {
+    name: Option<String>,
+    element: Option<String>,
+    atomic_symbol: Option<String>,
+    atomic_number: Option<u32>,
+    neutron_number: Option<u32>,
+    mass_number: Option<u32>,
+    metastable: Option<u32>,
+    atomic_weight_ratio: Option<f64>,
+    library: Option<String>,
+    fissionable: bool,
+    available_temperatures: Vec<String>,
+    #[serde(default)]
+    temperatures_without_data: Vec<String>,
+    temperatures: BTreeMap<String, TemperatureIndex>,
+}
+
+enum Storage {
+    Mapped(memmap2::Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Storage {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Storage::Mapped(map) => map,
+            Storage::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// A nuclide file in the binary format, opened without reading its arrays.
+///
+/// Files are memory mapped, so the energy grids and cross sections returned by
+/// [`NuclideBinary::energy`] and [`NuclideBinary::cross_section`] borrow
+/// directly from the file and only the pages that are used get read. The file
+/// must not be modified while it is open.
+pub struct NuclideBinary {
+    storage: Storage,
+    header: Header,
+    data_start: usize,
+}
+
+impl NuclideBinary {
+    /// Open a binary nuclide file by memory mapping it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read only, and the format documents that files
+        // must not be modified while they are open
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Self::new(Storage::Mapped(map))
+            .map_err(|e| format!("{}: {}", path.to_string_lossy(), e).into())
+    }
+
+    /// Read a binary nuclide from bytes already in memory (e.g. fetched in a browser).
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+        Self::new(Storage::Owned(bytes))
+    }
+
+    fn new(storage: Storage) -> Result<Self, String> {
+        let bytes = storage.bytes();
+        if bytes.len() < PREAMBLE_LEN || &bytes[..8] != BINARY_MAGIC {
+            return Err("Not a binary nuclide file, the file does not start with the expected magic bytes".to_string());
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if version != BINARY_VERSION {
+            return Err(format!(
+                "Unsupported binary nuclide format version {}, this version reads version {}",
+                version, BINARY_VERSION
+            ));
+        }
+        let header_len = u64::from_le_bytes(bytes[16..24].try_into().unwrap()) as usize;
+        let header_end = PREAMBLE_LEN
+            .checked_add(header_len)
+            .filter(|&end| end <= bytes.len())
+            .ok_or("Binary nuclide file is truncated inside its header")?;
+        let header: Header = serde_json::from_slice(&bytes[PREAMBLE_LEN..header_end])
+            .map_err(|e| format!("Invalid binary nuclide header: {}", e))?;
+        let data_start = padded(header_end);
+
+        // Check every indexed array lies inside the file, so later reads cannot fail
+        let data_len = bytes.len().saturating_sub(data_start) / 8;
+        for (temperature, index) in &header.temperatures {
+            let arrays = index.energy.iter().chain(
+                index
+                    .reactions
+                    .values()
+                    .flat_map(|r| std::iter::once(&r.cross_section).chain(r.energy.iter())),
+            );
+            for array in arrays {
+                if array.offset.checked_add(array.len).is_none_or(|end| end > data_len) {
+                    return Err(format!(
+                        "Binary nuclide file is truncated, an array of temperature {} ends past the end of the data",
+                        temperature
+                    ));
+                }
+            }
+        }
+        Ok(NuclideBinary { storage, header, data_start })
+    }
+
+    /// Nuclide name stored in the file.
+    pub fn name(&self) -> Option<&str> {
+        self.header.name.as_deref()
+    }
+
+    /// Every temperature listed in the original data, with or without arrays.
+    pub fn available_temperatures(&self) -> &[String] {
+        &self.header.available_temperatures
+    }
+
+    /// Temperatures with data in the file, sorted.
+    pub fn temperatures(&self) -> Vec<&str> {
+        self.header.temperatures.keys().map(String::as_str).collect()
+    }
+
+    /// MT numbers stored at `temperature`, sorted.
+    pub fn mts(&self, temperature: &str) -> Option<Vec<i32>> {
+        self.header
+            .temperatures
+            .get(temperature)
+            .map(|index| index.reactions.keys().copied().collect())
+    }
+
+    /// Energy grid in eV at `temperature`.
+    pub fn energy(&self, temperature: &str) -> Option<Cow<'_, [f64]>> {
+        let array = self.header.temperatures.get(temperature)?.energy?;
+        Some(self.array(array))
+    }
+
+    /// Cross section values in barns of `mt` at `temperature`, starting at the
+    /// reaction's `threshold_idx` in the energy grid.
+    pub fn cross_section(&self, temperature: &str, mt: i32) -> Option<Cow<'_, [f64]>> {
+        let reaction = self.header.temperatures.get(temperature)?.reactions.get(&mt)?;
+        Some(self.array(reaction.cross_section))
+    }
+
+    /// Values of an array, borrowed from the file when the host is
+    /// little-endian (the usual case) and copied otherwise.
+    fn array(&self, array: ArrayRef) -> Cow<'_, [f64]> {
+        let start = self.data_start + array.offset * 8;
+        let bytes = &self.storage.bytes()[start..start + array.len * 8];
+        if cfg!(target_endian = "little") {
+            if let Ok(values) = bytemuck::try_cast_slice::<u8, f64>(bytes) {
+                return Cow::Borrowed(values);
+            }
+        }
+        Cow::Owned(
+            bytes
+                .chunks_exact(8)
+                .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+                .collect(),
+        )
+    }
+
+    /// Build a [`Nuclide`] holding only the given temperatures and MT numbers.
+    ///
+    /// `None` or an empty set selects every MT and every temperature except
+    /// 0 K (see [`crate::temperature::default_temperatures`]). Only the arrays
+    /// of the selected data are read. Summed reactions are stored in the file, so
+    /// they are not reconstructed from the loaded MTs.
+    pub fn to_nuclide(
+        &self,
+        temperatures: Option<&HashSet<String>>,
+        mts: Option<&HashSet<i32>>,
+    ) -> Nuclide {
+        let header = &self.header;
+        let default_temperatures: HashSet<String>;
+        let temperatures = match temperatures {
+            Some(t) if !t.is_empty() => t,
+            _ => {
+                let keys: Vec<String> = header.temperatures.keys().cloned().collect();
+                default_temperatures = crate::temperature::default_temperatures(&keys).into_iter().collect();
+                &default_temperatures
+            }
+        };
+        let mut energy_map = HashMap::new();
+        let mut reactions = HashMap::new();
+        for (temperature, index) in &header.temperatures {
+            if !temperatures.contains(temperature) {
+                continue;
+            }
+            let grid = index.energy.map(|array| self.array(array).into_owned());
+            let mut temp_reactions = HashMap::new();
+            for (&mt, reaction) in &index.reactions {
+                if mts.is_some_and(|m| !m.is_empty() && !m.contains(&mt)) {
+                    continue;
+                }
+                // Reactions use the nuclide grid from threshold_idx unless they have their own
+                let energy = match (reaction.energy, &grid) {
+                    (Some(array), _) => self.array(array).into_owned(),
+                    (None, Some(grid)) if reaction.threshold_idx < grid.len() => {
+                        grid[reaction.threshold_idx..].to_vec()
+                    }
+                    _ => Vec::new(),
+                };
+                temp_reactions.insert(
+                    mt,
+                    Reaction {
+                        cross_section: self.array(reaction.cross_section).into_owned(),
+                        threshold_idx: reaction.threshold_idx,
+                        interpolation: reaction.interpolation.clone(),
+                        breakpoints: reaction.breakpoints.clone(),
+                        energy,
+                        mt_number: mt,
+                        derived: reaction.derived,
+                    },
+                );
+            }
+            if let Some(grid) = grid {
+                energy_map.insert(temperature.clone(), grid);
+            }
+            if !temp_reactions.is_empty() {
+                reactions.insert(temperature.clone(), temp_reactions);
+            }
+        }
+
+        let mut loaded_temperatures: Vec<String> = reactions.keys().cloned().collect();
+        loaded_temperatures.sort();
+        Nuclide {
+            name: header.name.clone(),
+            element: header.element.clone(),
+            atomic_symbol: header.atomic_symbol.clone(),
+            atomic_number: header.atomic_number,
+            neutron_number: header.neutron_number,
+            mass_number: header.mass_number,
+            metastable: header.metastable,
+            atomic_weight_ratio: header.atomic_weight_ratio,
+            library: header.library.clone(),
+            energy: if energy_map.is_empty() { None } else { Some(energy_map) },
+            reactions,
+            fissionable: header.fissionable,
+            available_temperatures: header.available_temperatures.clone(),
+            temperatures_without_data: header.temperatures_without_data.clone(),
+            loaded_temperatures,
+            data_path: None,
+        }
+    }
+}
+
+/// Round up to a multiple of 8 bytes.
+fn padded(len: usize) -> usize {
+    len.div_ceil(8) * 8
+}
+
+/// Arrays of the data section, in the order they are written.
+#[derive(Default)]
+struct DataSection<'a> {
+    arrays: Vec<&'a [f64]>,
+    len: usize,
+}
+
+impl<'a> DataSection<'a> {
+    fn add(&mut self, values: &'a [f64]) -> ArrayRef {
+        let array = ArrayRef { offset: self.len, len: values.len() };
+        self.arrays.push(values);
+        self.len += values.len();
+        array
+    }
+}
+
+/// Serialize a nuclide to the binary format.
+pub(crate) fn nuclide_to_binary(nuclide: &Nuclide) -> Result<Vec<u8>, String> {
+    let mut data = DataSection::default();
+    let mut temperatures = BTreeMap::new();
+    let mut keys: Vec<&String> = nuclide.reactions.keys().collect();
+    if let Some(energy_map) = &nuclide.energy {
+        keys.extend(energy_map.keys().filter(|t| !nuclide.reactions.contains_key(*t)));
+    }
+    // Sorted so the same nuclide always gives the same file, with the arrays of
+    // each temperature next to each other
+    keys.sort();
+    for temperature in keys {
+        let grid = nuclide.energy.as_ref().and_then(|e| e.get(temperature));
+        let energy = grid.map(|grid| data.add(grid));
+        let mut reactions = BTreeMap::new();
+        let mut mt_reactions: Vec<_> = nuclide.reactions.get(temperature).into_iter().flatten().collect();
+        mt_reactions.sort_by_key(|(&mt, _)| mt);
+        for (&mt, reaction) in mt_reactions {
+            let cross_section = data.add(&reaction.cross_section);
+            let on_nuclide_grid = reaction.energy.is_empty()
+                || grid.is_some_and(|g| g.get(reaction.threshold_idx..) == Some(&reaction.energy[..]));
+            let energy = if on_nuclide_grid {
+                None
+            } else {
+                Some(data.add(&reaction.energy))
+            };
+            reactions.insert(
+                mt,
+                ReactionIndex {
+                    threshold_idx: reaction.threshold_idx,
+                    interpolation: reaction.interpolation.clone(),
+                    breakpoints: reaction.breakpoints.clone(),
+                    derived: reaction.derived,
+                    cross_section,
+                    energy,
+                },
+            );
+        }
+        temperatures.insert(temperature.clone(), TemperatureIndex { energy, reactions });
+    }
+
+    let header = Header {
+        name: nuclide.name.clone(),
+        element: nuclide.element.clone(),
+        atomic_symbol: nuclide.atomic_symbol.clone(),
+        atomic_number: nuclide.atomic_number,
+        neutron_number: nuclide.neutron_number,
+        mass_number: nuclide.mass_number,
+        metastable: nuclide.metastable,
+        atomic_weight_ratio: nuclide.atomic_weight_ratio,
+        library: nuclide.library.clone(),
+        fissionable: nuclide.fissionable,
+        available_temperatures: nuclide.available_temperatures.clone(),
+        temperatures_without_data: nuclide.temperatures_without_data.clone(),
+        temperatures,
+    };
+    let header = serde_json::to_vec(&header).map_err(|e| e.to_string())?;
+
+    let data_start = padded(PREAMBLE_LEN + header.len());
+    let mut bytes = Vec::with_capacity(data_start + 8 * data.len);
+    bytes.extend_from_slice(BINARY_MAGIC);
+    bytes.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&(header.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&header);
+    bytes.resize(data_start, 0);
+    for values in data.arrays {
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    Ok(bytes)
+}
+
+/// True when the file at `path` starts with the binary nuclide magic bytes.
+pub(crate) fn is_binary_nuclide_file(path: &Path) -> bool {
+    let mut magic = [0u8; 8];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| &magic == BINARY_MAGIC)
+}
+
+/// Read a binary nuclide file, keeping only the given temperatures and MT
+/// numbers (`None` or an empty set keeps everything but 0 K data, as in
+/// [`NuclideBinary::to_nuclide`]).
+///
+/// `available_temperatures` always lists every temperature of the file.
+pub fn read_nuclide_from_binary<P: AsRef<Path>>(
+    path: P,
+    temperatures: Option<&HashSet<String>>,
+    mts: Option<&HashSet<i32>>,
+) -> Result<Nuclide, Box<dyn std::error::Error>> {
+    let binary = NuclideBinary::open(&path)?;
+    let mut nuclide = binary.to_nuclide(temperatures, mts);
+    nuclide.data_path = Some(path.as_ref().to_string_lossy().to_string());
+    Ok(nuclide)
+}
+
+/// Convert a nuclide JSON file to the binary format.
+///
+/// `json_path` may also be a nuclide name or keyword, resolved like in
+/// [`crate::nuclide::read_nuclide_from_json`]. Every temperature is kept,
+/// including 0 K data. Summed reactions reconstructed while reading the JSON
+/// are stored, flagged as derived.
+pub fn convert_json_to_binary<P: AsRef<Path>, Q: AsRef<Path>>(
+    json_path: P,
+    binary_path: Q,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let all = |available: &[String]| available.iter().cloned().collect();
+    let nuclide = crate::nuclide::read_nuclide_selecting(json_path, &all, None)?;
+    nuclide.export_to_binary(binary_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Unique per test run so concurrent runs don't share files
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("materials_for_mc_test_{}_{}", std::process::id(), name))
+    }
+
+    fn read_json(path: &str) -> Nuclide {
+        crate::nuclide::read_nuclide_from_json(path, None).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (json, name) in [("tests/Li6.json", "Li6.bin"), ("tests/Be9.json", "Be9.bin")] {
+            let nuclide = read_json(json);
+            let path = temp_path(name);
+            nuclide.export_to_binary(&path).unwrap();
+            let read = read_nuclide_from_binary(&path, None, None).unwrap();
+
+            assert_eq!(read.name, nuclide.name);
+            assert_eq!(read.atomic_weight_ratio, nuclide.atomic_weight_ratio);
+            assert_eq!(read.fissionable, nuclide.fissionable);
+            assert_eq!(read.available_temperatures, nuclide.available_temperatures);
+            assert_eq!(read.loaded_temperatures, nuclide.loaded_temperatures);
+            assert_eq!(read.energy, nuclide.energy);
+            assert_eq!(read.reactions, nuclide.reactions);
+            assert_eq!(read.data_path.as_deref(), Some(path.to_string_lossy().as_ref()));
+            std::fs::remove_file(&path).ok();
+        }
+    }
+
+    #[test]
+    fn test_selective_and_zero_copy_access() {
+        let nuclide = read_json("tests/Be9.json");
+        let binary = NuclideBinary::from_bytes(nuclide.to_binary().unwrap()).unwrap();
+        assert_eq!(binary.name(), Some("Be9"));
+        assert_eq!(binary.temperatures(), vec!["294", "300"]);
+        assert_eq!(binary.available_temperatures(), nuclide.available_temperatures);
+
+        let grid = binary.energy("294").unwrap();
+        assert_eq!(&grid[..], &nuclide.energy.as_ref().unwrap()["294"][..]);
+        let elastic = binary.cross_section("300", 2).unwrap();
+        assert_eq!(&elastic[..], &nuclide.reactions["300"][&2].cross_section[..]);
+        assert!(binary.cross_section("300", 9999).is_none());
+
+        let temperatures: HashSet<String> = ["300".to_string()].into_iter().collect();
+        let mts: HashSet<i32> = [2, 102].into_iter().collect();
+        let selected = binary.to_nuclide(Some(&temperatures), Some(&mts));
+        assert_eq!(selected.loaded_temperatures, vec!["300".to_string()]);
+        assert_eq!(selected.available_temperatures, nuclide.available_temperatures);
+        let mut loaded_mts: Vec<i32> = selected.reactions["300"].keys().copied().collect();
+        loaded_mts.sort();
+        assert_eq!(loaded_mts, vec![2, 102]);
+        assert_eq!(selected.reactions["300"][&102], nuclide.reactions["300"][&102]);
+
+        // Memory mapped files are page aligned, so arrays are read without copying
+        let path = temp_path("Be9_mapped.bin");
+        nuclide.export_to_binary(&path).unwrap();
+        let mapped = NuclideBinary::open(&path).unwrap();
+        let grid = mapped.energy("294").unwrap();
+        if cfg!(target_endian = "little") {
+            assert!(matches!(grid, Cow::Borrowed(_)));
+        }
+        assert_eq!(&grid[..], &nuclide.energy.as_ref().unwrap()["294"][..]);
+        drop(mapped);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_loaders_read_binary_files() {
+        let path = temp_path("Li7.bin");
+        convert_json_to_binary("tests/Li7.json", &path).unwrap();
+        assert!(is_binary_nuclide_file(&path));
+        assert!(!is_binary_nuclide_file(Path::new("tests/Li7.json")));
+
+        let json = read_json("tests/Li7.json");
+        let read = crate::nuclide::read_nuclide_from_json(&path, None).unwrap();
+        assert_eq!(read.reactions, json.reactions);
+
+        let mut map = HashMap::new();
+        map.insert("Li7".to_string(), path.to_string_lossy().to_string());
+        let cached = crate::nuclide::get_or_load_nuclide("Li7", &map, None).unwrap();
+        assert_eq!(cached.reactions, json.reactions);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        let bytes = read_json("tests/Li6.json").to_binary().unwrap();
+        let error = NuclideBinary::from_bytes(b"{\"name\": \"Li6\"}".to_vec()).err().unwrap();
+        assert!(error.contains("Not a binary nuclide file"), "{}", error);
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[8] = 9;
+        assert!(NuclideBinary::from_bytes(wrong_version).err().unwrap().contains("version 9"));
+
+        let truncated = bytes[..bytes.len() - 8].to_vec();
+        let error = NuclideBinary::from_bytes(truncated).err().unwrap();
+        assert!(error.contains("truncated"), "{}", error);
+    }
+}
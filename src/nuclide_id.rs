@@ -0,0 +1,191 @@
+// Provides a parsed nuclide identifier (Z, A, metastable state) for nuclide names
+use crate::data::{atomic_number, ELEMENT_SYMBOLS};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Identifier of a nuclide: atomic number, mass number and metastable state.
+///
+/// Parsed from GNDS style names such as `"Fe56"`, `"Am242_m1"` (first
+/// metastable state) or `"C0"`, the natural element evaluation of carbon that
+/// some libraries provide in place of isotopic data. Natural elements have a
+/// mass number of 0. Formatting an identifier gives back its canonical name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NuclideId {
+    /// Atomic (proton) number Z
+    pub atomic_number: u32,
+    /// Mass number A (0 for a natural element)
+    pub mass_number: u32,
+    /// Metastable state (0 for the ground state)
+    pub metastable: u32,
+}
+
+impl NuclideId {
+    /// Create an identifier, checking Z is a known element and A is possible for it.
+    pub fn new(atomic_number: u32, mass_number: u32, metastable: u32) -> Result<Self, String> {
+        if atomic_number == 0 || atomic_number as usize > ELEMENT_SYMBOLS.len() {
+            return Err(format!("Invalid atomic number {}", atomic_number));
+        }
+        if mass_number != 0 && (mass_number < atomic_number || mass_number >= 300) {
+            return Err(format!(
+                "Invalid mass number {} for {}",
+                mass_number,
+                ELEMENT_SYMBOLS[atomic_number as usize - 1]
+            ));
+        }
+        if mass_number == 0 && metastable > 0 {
+            return Err(format!(
+                "Natural element {} cannot have a metastable state",
+                ELEMENT_SYMBOLS[atomic_number as usize - 1]
+            ));
+        }
+        Ok(NuclideId {
+            atomic_number,
+            mass_number,
+            metastable,
+        })
+    }
+
+    /// Parse a nuclide name such as `"Li6"`, `"Am242_m1"` or `"C0"`.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid nuclide name '{}', expected an element symbol and mass number such as 'Fe56', 'Am242_m1' or 'C0'",
+                name
+            )
+        };
+        let (ground, metastable) = match name.split_once("_m") {
+            Some((ground, state)) => {
+                if state.is_empty() || !state.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                (ground, state.parse::<u32>().map_err(|_| invalid())?)
+            }
+            None => (name, 0),
+        };
+        let split = ground
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let (symbol, mass_number) = ground.split_at(split);
+        if !mass_number.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let z = atomic_number(symbol).ok_or_else(invalid)?;
+        let a: u32 = mass_number.parse().map_err(|_| invalid())?;
+        NuclideId::new(z, a, metastable).map_err(|e| format!("{}: {}", invalid(), e))
+    }
+
+    /// Identifier of the natural element evaluation (e.g. `C0`) of an element symbol.
+    pub fn natural(symbol: &str) -> Result<Self, String> {
+        let z = atomic_number(symbol)
+            .ok_or_else(|| format!("Unknown element symbol '{}'", symbol))?;
+        NuclideId::new(z, 0, 0)
+    }
+
+    /// Element symbol (e.g. `"Am"` for `Am242_m1`).
+    pub fn symbol(&self) -> &'static str {
+        ELEMENT_SYMBOLS[self.atomic_number as usize - 1]
+    }
+
+    /// Neutron number N = A - Z, `None` for a natural element.
+    pub fn neutron_number(&self) -> Option<u32> {
+        if self.is_natural() {
+            None
+        } else {
+            Some(self.mass_number - self.atomic_number)
+        }
+    }
+
+    /// Whether this is a natural element evaluation (mass number 0).
+    pub fn is_natural(&self) -> bool {
+        self.mass_number == 0
+    }
+
+    /// Whether this is an excited metastable state.
+    pub fn is_metastable(&self) -> bool {
+        self.metastable > 0
+    }
+
+    /// The ground state of the nuclide (e.g. `Am242` for `Am242_m1`).
+    pub fn ground_state(&self) -> NuclideId {
+        NuclideId {
+            metastable: 0,
+            ..*self
+        }
+    }
+
+    /// Canonical name of the nuclide (e.g. `"Am242_m1"`).
+    pub fn name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for NuclideId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.symbol(), self.mass_number)?;
+        if self.metastable > 0 {
+            write!(f, "_m{}", self.metastable)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NuclideId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NuclideId::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ground_state() {
+        let id = NuclideId::parse("Fe56").unwrap();
+        assert_eq!((id.atomic_number, id.mass_number, id.metastable), (26, 56, 0));
+        assert_eq!(id.symbol(), "Fe");
+        assert_eq!(id.neutron_number(), Some(30));
+        assert!(!id.is_natural() && !id.is_metastable());
+        assert_eq!(id.to_string(), "Fe56");
+    }
+
+    #[test]
+    fn test_parse_metastable() {
+        let id: NuclideId = "Am242_m1".parse().unwrap();
+        assert_eq!((id.atomic_number, id.mass_number, id.metastable), (95, 242, 1));
+        assert!(id.is_metastable());
+        assert_eq!(id.ground_state().name(), "Am242");
+        assert_eq!(id.name(), "Am242_m1");
+    }
+
+    #[test]
+    fn test_parse_natural_element() {
+        let carbon = NuclideId::parse("C0").unwrap();
+        assert_eq!((carbon.atomic_number, carbon.mass_number), (6, 0));
+        assert!(carbon.is_natural());
+        assert_eq!(carbon.neutron_number(), None);
+        assert_eq!(NuclideId::natural("V").unwrap().name(), "V0");
+        assert!(NuclideId::natural("Xx").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_names() {
+        for name in ["Fe", "56", "Xx56", "Fe56_m", "Fe56_mx", "Fe5a6", "H0_m1", "U2", "Fe300", ""] {
+            assert!(NuclideId::parse(name).is_err(), "{} should not parse", name);
+        }
+    }
+
+    #[test]
+    fn test_ordering_by_z_then_a() {
+        let mut ids: Vec<NuclideId> = ["U235", "Li7", "Li6", "Am242_m1", "Am242"]
+            .iter()
+            .map(|n| n.parse().unwrap())
+            .collect();
+        ids.sort();
+        let names: Vec<String> = ids.iter().map(NuclideId::name).collect();
+        assert_eq!(names, vec!["Li6", "Li7", "U235", "Am242", "Am242_m1"]);
+    }
+}
@@ -0,0 +1,222 @@
+// Provides conversions between the nuclide name formats used by different codes
+use crate::data::{atomic_mass, atomic_number, ELEMENT_SYMBOLS};
+use crate::nuclide_id::NuclideId;
+
+/// Parse a nuclide given in any common form into its identifier.
+///
+/// Accepted forms, all case insensitive in the element symbol:
+/// * GNDS names: `"Li6"`, `"U235_m1"`, `"C0"`
+/// * lowercase or uppercase names: `"li6"`, `"LI6"`
+/// * hyphenated names: `"Li-6"`, `"Am-242m"`, `"Am-242m1"`, `"C-nat"`
+/// * metastable suffixes without underscore: `"Am242m"`, `"Am242m1"`
+/// * ZAIDs with or without a library suffix: `"3006"`, `"3006.80c"`, `"92635"`,
+///   where metastable states use the MCNP offset `A + 300 + 100 * m` (except
+///   Am242, see [`to_zaid`])
+pub fn parse_nuclide_name(name: &str) -> Result<NuclideId, String> {
+    let trimmed = name.trim();
+    if trimmed.starts_with(|c: char| c.is_ascii_digit()) {
+        return zaid_to_id(trimmed);
+    }
+
+    let invalid = || {
+        format!(
+            "Cannot interpret '{}' as a nuclide, expected a name such as 'Li6', 'Li-6', 'U235_m1' or a ZAID such as '3006'",
+            name
+        )
+    };
+    let split = trimmed
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(trimmed.len());
+    let (letters, rest) = trimmed.split_at(split);
+    let mut chars = letters.chars();
+    let first = chars.next().ok_or_else(invalid)?;
+    let symbol: String = first
+        .to_uppercase()
+        .chain(chars.flat_map(char::to_lowercase))
+        .collect();
+
+    let rest = rest.strip_prefix('-').unwrap_or(rest).to_ascii_lowercase();
+    let (symbol, rest) = match atomic_number(&symbol) {
+        Some(_) => (symbol, rest),
+        // "Cnat" has no separator before "nat"
+        None => match symbol.to_ascii_lowercase().strip_suffix("nat") {
+            Some(stripped) if rest.is_empty() && !stripped.is_empty() => {
+                let mut chars = stripped.chars();
+                let first = chars.next().ok_or_else(invalid)?;
+                let symbol: String = first.to_uppercase().chain(chars).collect();
+                (symbol, String::from("nat"))
+            }
+            _ => return Err(invalid()),
+        },
+    };
+    let z = atomic_number(&symbol).ok_or_else(invalid)?;
+    if rest == "nat" {
+        return NuclideId::new(z, 0, 0);
+    }
+
+    let digits_end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (mass_number, suffix) = rest.split_at(digits_end);
+    let a: u32 = mass_number.parse().map_err(|_| invalid())?;
+    let metastable_state = suffix.strip_prefix('_').unwrap_or(suffix);
+    let metastable = match metastable_state.strip_prefix('m') {
+        None if suffix.is_empty() => 0,
+        Some("") if !suffix.starts_with('_') => 1,
+        Some(state) if !state.is_empty() && state.chars().all(|c| c.is_ascii_digit()) => {
+            state.parse().map_err(|_| invalid())?
+        }
+        _ => return Err(invalid()),
+    };
+    NuclideId::new(z, a, metastable).map_err(|e| format!("{}: {}", invalid(), e))
+}
+
+/// ZAIDs of the Am242 ground and first metastable states, which MCNP and NJOY
+/// swap relative to the generic metastable convention.
+const AM242_ZAID: u32 = 95642;
+const AM242_M1_ZAID: u32 = 95242;
+
+/// Decode a ZAID (e.g. `"26056"`, `"26056.80c"`, `"92635"`) into an identifier.
+///
+/// Any library suffix is ignored and a mass number of 0 is a natural element.
+/// For metastable ZAIDs (`A + 300 + 100 * m`) the lowest state giving a
+/// nuclide in the atomic mass database is chosen. 95242 is Am242_m1 and
+/// 95642 is the Am242 ground state.
+pub(crate) fn zaid_to_id(zaid: &str) -> Result<NuclideId, String> {
+    let invalid = || format!("Invalid ZAID '{}'", zaid);
+    let number = zaid.split('.').next().unwrap_or_default();
+    let value: u32 = number.parse().map_err(|_| invalid())?;
+    match value {
+        AM242_ZAID => return NuclideId::new(95, 242, 0),
+        AM242_M1_ZAID => return NuclideId::new(95, 242, 1),
+        _ => {}
+    }
+    let z = value / 1000;
+    let aaa = value % 1000;
+    if z == 0 || z as usize > ELEMENT_SYMBOLS.len() {
+        return Err(invalid());
+    }
+    if aaa < 300 {
+        return NuclideId::new(z, aaa, 0).map_err(|_| invalid());
+    }
+    let symbol = ELEMENT_SYMBOLS[z as usize - 1];
+    for metastable in 1..=4 {
+        if aaa < 300 + 100 * metastable {
+            break;
+        }
+        let a = aaa - 300 - 100 * metastable;
+        if a >= z && atomic_mass(&format!("{}{}", symbol, a)).is_some() {
+            return NuclideId::new(z, a, metastable).map_err(|_| invalid());
+        }
+    }
+    Err(invalid())
+}
+
+/// Canonical GNDS name of a nuclide given in any form accepted by
+/// [`parse_nuclide_name`] (e.g. `"Li-6"`, `"3006.80c"` -> `"Li6"`).
+pub fn to_gnds_name(name: &str) -> Result<String, String> {
+    parse_nuclide_name(name).map(|id| id.name())
+}
+
+/// ZAID of a nuclide given in any form accepted by [`parse_nuclide_name`].
+///
+/// The ZAID is `Z * 1000 + A`, with `A = 0` for natural elements. Metastable
+/// states use the MCNP convention `Z * 1000 + A + 300 + 100 * m`, so
+/// `"U235_m1"` becomes 92635. As in MCNP and NJOY libraries, Am242 is the
+/// exception: `"Am242_m1"` is 95242 and the ground state `"Am242"` is 95642.
+pub fn to_zaid(name: &str) -> Result<u32, String> {
+    let id = parse_nuclide_name(name)?;
+    if (id.atomic_number, id.mass_number) == (95, 242) {
+        match id.metastable {
+            0 => return Ok(AM242_ZAID),
+            1 => return Ok(AM242_M1_ZAID),
+            _ => {}
+        }
+    }
+    let a = if id.is_metastable() {
+        id.mass_number + 300 + 100 * id.metastable
+    } else {
+        id.mass_number
+    };
+    Ok(id.atomic_number * 1000 + a)
+}
+
+/// Hyphenated name of a nuclide given in any form accepted by
+/// [`parse_nuclide_name`] (e.g. `"Li-6"`, `"Am-242m1"` or `"C-nat"`).
+pub fn to_hyphenated_name(name: &str) -> Result<String, String> {
+    let id = parse_nuclide_name(name)?;
+    Ok(match (id.is_natural(), id.metastable) {
+        (true, _) => format!("{}-nat", id.symbol()),
+        (false, 0) => format!("{}-{}", id.symbol(), id.mass_number),
+        (false, m) => format!("{}-{}m{}", id.symbol(), id.mass_number, m),
+    })
+}
+
+/// Canonical GNDS name of a nuclide, or the input unchanged when it is not a
+/// recognisable nuclide (e.g. data source keywords or custom names).
+pub(crate) fn canonical_nuclide_name(name: &str) -> String {
+    to_gnds_name(name).unwrap_or_else(|_| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_gnds_name_from_common_forms() {
+        for name in ["Li6", "li6", "LI6", "Li-6", "li-6", "3006", "3006.80c", " Li6 "] {
+            assert_eq!(to_gnds_name(name).unwrap(), "Li6", "{}", name);
+        }
+        for name in ["U235_m1", "U235m", "U235m1", "U-235m", "u-235m1", "92635"] {
+            assert_eq!(to_gnds_name(name).unwrap(), "U235_m1", "{}", name);
+        }
+        assert_eq!(to_gnds_name("Am242_m2").unwrap(), "Am242_m2");
+        assert_eq!(to_gnds_name("95242.00c").unwrap(), "Am242_m1");
+        assert_eq!(to_gnds_name("95642.00c").unwrap(), "Am242");
+        for name in ["C0", "c-nat", "Cnat", "C-0", "6000", "6000.80c"] {
+            assert_eq!(to_gnds_name(name).unwrap(), "C0", "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_to_gnds_name_invalid() {
+        for name in ["", "H", "Fe", "Xx56", "Li-", "Li6_m", "Li6x", "Li6_x1", "0", "999999", "tendl-21"] {
+            assert!(to_gnds_name(name).is_err(), "{} should not convert", name);
+        }
+    }
+
+    #[test]
+    fn test_to_zaid() {
+        assert_eq!(to_zaid("Li6").unwrap(), 3006);
+        assert_eq!(to_zaid("Li-6").unwrap(), 3006);
+        assert_eq!(to_zaid("Am242_m1").unwrap(), 95242);
+        assert_eq!(to_zaid("Am-242").unwrap(), 95642);
+        assert_eq!(to_zaid("Am242_m2").unwrap(), 95742);
+        assert_eq!(to_zaid("U-235m").unwrap(), 92635);
+        assert_eq!(to_zaid("C0").unwrap(), 6000);
+        assert_eq!(to_zaid("26056.80c").unwrap(), 26056);
+    }
+
+    #[test]
+    fn test_to_hyphenated_name() {
+        assert_eq!(to_hyphenated_name("Li6").unwrap(), "Li-6");
+        assert_eq!(to_hyphenated_name("Am242_m1").unwrap(), "Am-242m1");
+        assert_eq!(to_hyphenated_name("6000").unwrap(), "C-nat");
+        assert!(to_hyphenated_name("Fe").is_err());
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for name in ["H1", "Li7", "Fe56", "U238", "Am242", "Am242_m1", "V0"] {
+            assert_eq!(to_gnds_name(&to_zaid(name).unwrap().to_string()).unwrap(), name);
+            assert_eq!(to_gnds_name(&to_hyphenated_name(name).unwrap()).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn test_canonical_nuclide_name_keeps_unknown_names() {
+        assert_eq!(canonical_nuclide_name("li-7"), "Li7");
+        assert_eq!(canonical_nuclide_name("tendl-21"), "tendl-21");
+        assert_eq!(canonical_nuclide_name("Fe"), "Fe");
+    }
+}
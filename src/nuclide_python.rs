@@ -31,11 +31,14 @@ pub struct PyNuclide {
     pub atomic_number: Option<u32>,
     pub neutron_number: Option<u32>,
     pub mass_number: Option<u32>,
+    pub metastable: Option<u32>,
+    pub atomic_weight_ratio: Option<f64>,
     pub library: Option<String>,
     pub energy: Option<HashMap<String, Vec<f64>>>,
     pub reactions: HashMap<String, HashMap<i32, Reaction>>,
     pub fissionable: bool,
     pub available_temperatures: Vec<String>,
+    pub temperatures_without_data: Vec<String>,
     pub loaded_temperatures: Vec<String>,
     pub data_path: Option<String>,
 }
@@ -97,6 +100,34 @@ impl PyNuclide {
         self.mass_number
     }
 
+    /// Metastable state, 0 for the ground state.
+    ///
+    /// Returns:
+    ///     Optional[int]: Metastable state or None if data not loaded.
+    #[getter]
+    pub fn metastable(&self) -> Option<u32> {
+        self.metastable
+    }
+
+    /// Ratio of the nuclide mass to the neutron mass (AWR).
+    ///
+    /// Returns:
+    ///     Optional[float]: Atomic weight ratio or None if data not loaded.
+    #[getter]
+    pub fn atomic_weight_ratio(&self) -> Option<f64> {
+        self.atomic_weight_ratio
+    }
+
+    /// Atomic mass in g/mol computed from the atomic weight ratio.
+    ///
+    /// Returns:
+    ///     Optional[float]: Atomic mass or None if the atomic weight ratio is unknown.
+    #[getter]
+    pub fn atomic_mass(&self) -> Option<f64> {
+        self.atomic_weight_ratio
+            .map(|awr| awr * crate::data::NEUTRON_MASS)
+    }
+
     /// Originating nuclear data library identifier.
     ///
     /// Returns:
@@ -159,11 +190,14 @@ impl PyNuclide {
             atomic_number: None,
             neutron_number: None,
             mass_number: None,
+            metastable: None,
+            atomic_weight_ratio: None,
             library: None,
             energy: None,
             reactions: HashMap::new(),
             fissionable: false,
             available_temperatures: Vec::new(),
+            temperatures_without_data: Vec::new(),
             loaded_temperatures: Vec::new(),
             data_path: None,
         }
@@ -179,6 +213,9 @@ impl PyNuclide {
     /// `available_temperatures` always lists every temperature present in the
     /// file. The subset actually loaded is stored in `loaded_temperatures`.
     ///
+    /// Files in the binary format (see `convert_json_to_binary`) and ACE files
+    /// are also accepted and recognized automatically.
+    ///
     /// Args:
     ///     path (Optional[str]): Optional path to the nuclide JSON file, keyword 
     ///         (e.g. "tendl-21", "fendl-3.2c"), or filesystem path. If provided,
@@ -242,7 +279,10 @@ impl PyNuclide {
     ///
     ///         - cross_section (List[float])
     ///         - threshold_idx (int)
-    ///         - interpolation (List[int])
+    ///         - interpolation (List[int]): ENDF interpolation law of each region
+    ///         - breakpoints (List[int]): ENDF breakpoints, the 1-based index of the
+    ///           last point of each region (empty for a single region)
+    ///         - derived (bool): True when summed from component reactions
     ///         - energy (Optional[List[float]]): Present when reaction has its own grid
     #[getter]
     pub fn reactions(&self, py: Python) -> PyResult<PyObject> {
@@ -256,6 +296,8 @@ impl PyNuclide {
                 reaction_dict.set_item("cross_section", &reaction.cross_section)?;
                 reaction_dict.set_item("threshold_idx", reaction.threshold_idx)?;
                 reaction_dict.set_item("interpolation", &reaction.interpolation)?;
+                reaction_dict.set_item("breakpoints", &reaction.breakpoints)?;
+                reaction_dict.set_item("derived", reaction.derived)?;
                 if !reaction.energy.is_empty() {
                     reaction_dict.set_item("energy", &reaction.energy)?;
                 }
@@ -330,8 +372,13 @@ impl PyNuclide {
     /// Args:
     ///     reaction (Union[int, str]): Either an ENDF/MT number (int) or reaction name (str) 
     ///         like "(n,gamma)", "(n,elastic)", "fission", etc.
-    ///     temperature (Optional[str]): Temperature to use. If None, uses the single
+    ///     temperature (Optional[Union[str, float]]): Temperature to use. A string must
+    ///         match a loaded temperature unless `method` is given, while a number in K
+    ///         may fall between the temperatures in the data. If None, uses the single
     ///         loaded temperature if only one is available.
+    ///     method (Optional[str]): How to evaluate temperatures between those in the
+    ///         data: "nearest" (default for numbers), "interpolation", "stochastic" or
+    ///         "broadening".
     ///
     /// Returns:
     ///     Tuple[List[float], List[float]]: A tuple of (cross_section_values, energy_grid).
@@ -339,30 +386,165 @@ impl PyNuclide {
     /// Raises:
     ///     Exception: If temperature not found, reaction not found, multiple temperatures loaded
     ///         without specifying one, or no data available.
+    #[pyo3(signature = (reaction, temperature=None, method=None))]
     pub fn microscopic_cross_section(
         &self,
         reaction: &PyAny,
-        temperature: Option<&str>,
+        temperature: Option<&PyAny>,
+        method: Option<&str>,
     ) -> PyResult<(Vec<f64>, Vec<f64>)> {
+        use crate::nuclide::ReactionIdentifier;
+        use crate::temperature::{parse_temperature, TemperatureMethod};
+        use pyo3::exceptions::{PyTypeError, PyValueError};
+
         let mut nuclide: Nuclide = self.clone().into();
         
         // Handle both integer and string inputs
-        let result = if let Ok(mt_num) = reaction.extract::<i32>() {
-            nuclide.microscopic_cross_section(mt_num, temperature)
+        let reaction: ReactionIdentifier = if let Ok(mt_num) = reaction.extract::<i32>() {
+            mt_num.into()
         } else if let Ok(reaction_name) = reaction.extract::<String>() {
-            nuclide.microscopic_cross_section(reaction_name, temperature)
+            reaction_name.into()
         } else {
-            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            return Err(PyErr::new::<PyTypeError, _>(
                 "reaction must be either an integer (MT number) or string (reaction name)"
             ));
         };
+
+        let method: Option<TemperatureMethod> = method
+            .map(str::parse)
+            .transpose()
+            .map_err(PyValueError::new_err)?;
+        let label: Option<String> = temperature.and_then(|t| t.extract::<String>().ok());
+        let result = match (temperature, label) {
+            (None, _) => nuclide.microscopic_cross_section(reaction, None),
+            (Some(_), Some(label)) => match method {
+                None => nuclide.microscopic_cross_section(reaction, Some(&label)),
+                Some(method) => {
+                    let kelvin = parse_temperature(&label).ok_or_else(|| {
+                        PyValueError::new_err(format!("Invalid temperature '{}'", label))
+                    })?;
+                    nuclide.microscopic_cross_section_at_temperature(reaction, kelvin, method)
+                }
+            },
+            (Some(t), None) => {
+                let kelvin: f64 = t.extract().map_err(|_| {
+                    PyTypeError::new_err("temperature must be a number in K or a string")
+                })?;
+                nuclide.microscopic_cross_section_at_temperature(
+                    reaction,
+                    kelvin,
+                    method.unwrap_or_default(),
+                )
+            }
+        };
         
         match result {
             Ok((cross_section, energy)) => Ok((cross_section, energy)),
-            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())),
+            Err(e) => Err(PyErr::new::<PyValueError, _>(e.to_string())),
         }
     }
 
+    /// Doppler broaden the cross sections to a new temperature.
+    ///
+    /// The 0 K data, or the lowest loaded temperature, is broadened with the
+    /// exact free gas kernel (SIGMA1 method) and stored as a new temperature.
+    ///
+    /// Args:
+    ///     temperature (float): Temperature in K to broaden to.
+    ///
+    /// Returns:
+    ///     str: Temperature key of the broadened data (e.g. "600"), which can be
+    ///     passed to microscopic_cross_section.
+    ///
+    /// Raises:
+    ///     ValueError: If no data is loaded, the atomic weight ratio is unknown or
+    ///         the temperature is below the loaded temperatures.
+    ///
+    /// Example:
+    ///     >>> nuclide = Nuclide("Be9")
+    ///     >>> nuclide.read_nuclide_from_json("tests/Be9.json", temperatures=["294"])
+    ///     >>> key = nuclide.doppler_broaden(600.0)
+    ///     >>> xs, energy = nuclide.microscopic_cross_section(2, key)
+    #[pyo3(text_signature = "(self, temperature)")]
+    pub fn doppler_broaden(&mut self, temperature: f64) -> PyResult<String> {
+        let mut nuclide: Nuclide = self.clone().into();
+        let key = nuclide
+            .doppler_broaden(temperature)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        *self = PyNuclide::from(nuclide);
+        Ok(key)
+    }
+
+    /// Check the loaded data for consistency.
+    ///
+    /// Reports non-monotonic or repeated energy grid points, negative cross
+    /// sections, threshold indices beyond the energy grid, cross sections whose
+    /// length does not match their energy grid, MT 1 differing from the sum of
+    /// its partials and declared temperatures without data.
+    ///
+    /// Args:
+    ///     tolerance (Optional[float]): Relative tolerance for comparing MT 1 with
+    ///         the sum of its partials. Defaults to 1e-6.
+    ///
+    /// Returns:
+    ///     ValidationReport: The issues found, empty when the data is consistent.
+    ///
+    /// Example:
+    ///     >>> nuclide = Nuclide("Li6")
+    ///     >>> nuclide.read_nuclide_from_json("tests/Li6.json")
+    ///     >>> report = nuclide.validate()
+    ///     >>> report.is_valid
+    ///     True
+    #[pyo3(signature = (tolerance=None), text_signature = "(self, tolerance=None)")]
+    pub fn validate(&self, tolerance: Option<f64>) -> crate::validation_python::PyValidationReport {
+        let nuclide: Nuclide = self.clone().into();
+        let inner = match tolerance {
+            Some(tolerance) => nuclide.validate_with_tolerance(tolerance),
+            None => nuclide.validate(),
+        };
+        crate::validation_python::PyValidationReport { inner }
+    }
+
+    /// Write the nuclide to a file in the compact binary format.
+    ///
+    /// Binary files load much faster than JSON and can be used in place of the
+    /// JSON file anywhere a nuclear data path is accepted, for example in
+    /// `Config.set_cross_section` or `read_nuclide_from_json`.
+    ///
+    /// Args:
+    ///     path (str): Path of the binary file to write.
+    ///
+    /// Returns:
+    ///     None
+    ///
+    /// Raises:
+    ///     OSError: If the file cannot be written.
+    #[pyo3(text_signature = "(self, path)")]
+    pub fn export_to_binary(&self, path: &str) -> PyResult<()> {
+        let nuclide: Nuclide = self.clone().into();
+        nuclide
+            .export_to_binary(path)
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Reconstruct summed reactions missing from the loaded data.
+    ///
+    /// Redundant reactions such as MT 1 (total), 3 (nonelastic), 4 (inelastic),
+    /// 27 (absorption), 101 (disappearance) and 103-107 (charged particle
+    /// production) are summed from their components following the ENDF
+    /// hierarchy. This is done automatically when data is read, so it is only
+    /// needed after editing the reactions.
+    ///
+    /// Returns:
+    ///     List[int]: MT numbers of all derived reactions.
+    #[pyo3(text_signature = "(self)")]
+    pub fn reconstruct_redundant_reactions(&mut self) -> Vec<i32> {
+        let mut nuclide: Nuclide = self.clone().into();
+        let derived = nuclide.reconstruct_redundant_reactions();
+        *self = PyNuclide::from(nuclide);
+        derived
+    }
+
     /// Sample a reaction based on cross sections at a given energy and temperature.
     ///
     /// This method randomly selects a nuclear reaction channel based on the relative 
@@ -381,7 +563,8 @@ impl PyNuclide {
     ///         - mt_number (int): ENDF/MT number of the sampled reaction
     ///         - cross_section (List[float]): Cross section values in barns
     ///         - threshold_idx (int): Index where reaction becomes active
-    ///         - interpolation (List[int]): Interpolation flags
+    ///         - interpolation (List[int]): ENDF interpolation law of each region
+    ///         - breakpoints (List[int]): ENDF interpolation breakpoints
     ///         - energy (List[float]): Reaction energy grid
     ///     Returns None if no reaction could be sampled (e.g., zero total cross section).
     ///
@@ -426,6 +609,7 @@ impl PyNuclide {
                 reaction_dict.set_item("cross_section", &reaction.cross_section)?;
                 reaction_dict.set_item("threshold_idx", reaction.threshold_idx)?;
                 reaction_dict.set_item("interpolation", &reaction.interpolation)?;
+                reaction_dict.set_item("breakpoints", &reaction.breakpoints)?;
                 reaction_dict.set_item("energy", &reaction.energy)?;
                 Ok(Some(reaction_dict.into()))
             })
@@ -445,11 +629,14 @@ impl From<Nuclide> for PyNuclide {
             atomic_number: n.atomic_number,
             neutron_number: n.neutron_number,
             mass_number: n.mass_number,
+            metastable: n.metastable,
+            atomic_weight_ratio: n.atomic_weight_ratio,
             library: n.library,
             energy: n.energy,
             reactions: n.reactions,
             fissionable: n.fissionable,
             available_temperatures: n.available_temperatures,
+            temperatures_without_data: n.temperatures_without_data,
             loaded_temperatures: n.loaded_temperatures,
             data_path: n.data_path,
         }
@@ -465,11 +652,14 @@ impl From<PyNuclide> for Nuclide {
             atomic_number: py.atomic_number,
             neutron_number: py.neutron_number,
             mass_number: py.mass_number,
+            metastable: py.metastable,
+            atomic_weight_ratio: py.atomic_weight_ratio,
             library: py.library,
             energy: py.energy,
             reactions: py.reactions,
             fissionable: py.fissionable,
             available_temperatures: py.available_temperatures,
+            temperatures_without_data: py.temperatures_without_data,
             loaded_temperatures: py.loaded_temperatures,
             data_path: py.data_path,
         }
@@ -495,6 +685,85 @@ pub fn py_read_nuclide_from_json(path: &str) -> PyResult<PyNuclide> {
     Ok(PyNuclide::from(nuclide))
 }
 
+#[cfg(feature = "pyo3")]
+#[pyfunction]
+/// Read a nuclide from a binary file, loading only the requested data.
+///
+/// Only the arrays of the selected temperatures and MT numbers are read from
+/// the file. `available_temperatures` always lists every temperature in it.
+///
+/// Args:
+///     path (str): Path to a binary nuclide file (see `convert_json_to_binary`).
+///     temperatures (Optional[List[str]]): Temperatures to load, all when omitted.
+///     mts (Optional[List[int]]): MT numbers to load, all when omitted.
+///
+/// Returns:
+///     Nuclide: The nuclide with the selected data loaded.
+///
+/// Raises:
+///     OSError: If the file cannot be opened or is not a valid binary nuclide file.
+#[pyo3(name = "read_nuclide_from_binary", signature = (path, temperatures=None, mts=None), text_signature = "(path, temperatures=None, mts=None)")]
+pub fn py_read_nuclide_from_binary(
+    path: &str,
+    temperatures: Option<Vec<String>>,
+    mts: Option<Vec<i32>>,
+) -> PyResult<PyNuclide> {
+    use std::collections::HashSet;
+    let temperatures: Option<HashSet<String>> = temperatures.map(|v| v.into_iter().collect());
+    let mts: Option<HashSet<i32>> = mts.map(|v| v.into_iter().collect());
+    let nuclide = crate::nuclide_binary::read_nuclide_from_binary(path, temperatures.as_ref(), mts.as_ref())
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    Ok(PyNuclide::from(nuclide))
+}
+
+#[cfg(feature = "pyo3")]
+#[pyfunction]
+/// Read a nuclide from a continuous-energy neutron ACE file.
+///
+/// ASCII (type 1) and binary (type 2) files are supported. Each table in the
+/// file becomes one temperature. ACE files can also be used anywhere a JSON
+/// path is accepted, for example in `Config.set_cross_section`.
+///
+/// Args:
+///     path (str): Path to the ACE file.
+///     temperatures (Optional[List[str]]): Temperatures to load (e.g. ["294"]),
+///         all when omitted.
+///
+/// Returns:
+///     Nuclide: The nuclide with the energy grid, reactions, atomic weight
+///     ratio and temperatures of the file.
+///
+/// Raises:
+///     OSError: If the file cannot be read or is not a valid ACE file.
+#[pyo3(name = "read_nuclide_from_ace", signature = (path, temperatures=None), text_signature = "(path, temperatures=None)")]
+pub fn py_read_nuclide_from_ace(path: &str, temperatures: Option<Vec<String>>) -> PyResult<PyNuclide> {
+    let temperatures: Option<std::collections::HashSet<String>> =
+        temperatures.map(|v| v.into_iter().collect());
+    let nuclide = crate::ace::read_nuclide_from_ace(path, temperatures.as_ref())
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+    Ok(PyNuclide::from(nuclide))
+}
+
+#[cfg(feature = "pyo3")]
+#[pyfunction]
+/// Convert a nuclide JSON file to the compact binary format.
+///
+/// Args:
+///     json_path (str): Path to the nuclide JSON file, or a nuclide name
+///         configured with `Config.set_cross_section`.
+///     binary_path (str): Path of the binary file to write.
+///
+/// Returns:
+///     None
+///
+/// Raises:
+///     OSError: If the JSON cannot be read or the binary file cannot be written.
+#[pyo3(name = "convert_json_to_binary", text_signature = "(json_path, binary_path)")]
+pub fn py_convert_json_to_binary(json_path: &str, binary_path: &str) -> PyResult<()> {
+    crate::nuclide_binary::convert_json_to_binary(json_path, binary_path)
+        .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+}
+
 #[cfg(feature = "pyo3")]
 #[pyfunction]
 /// Clear any internally cached nuclide data.
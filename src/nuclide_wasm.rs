@@ -111,6 +111,34 @@ impl WasmNuclide {
         }
     }
 
+    /// Load a nuclide from the bytes of a binary nuclide file (see
+    /// [`crate::NuclideBinary`]), e.g. fetched by the browser.
+    #[wasm_bindgen]
+    pub fn load_from_binary(bytes: &[u8]) -> Result<WasmNuclide, JsValue> {
+        let binary = crate::NuclideBinary::from_bytes(bytes.to_vec())
+            .map_err(|e| JsValue::from_str(&format!("Failed to read binary nuclide: {}", e)))?;
+        Ok(WasmNuclide {
+            inner: Arc::new(binary.to_nuclide(None, None)),
+        })
+    }
+
+    /// Load a nuclide from the bytes of a continuous-energy neutron ACE file,
+    /// ASCII or binary.
+    #[wasm_bindgen]
+    pub fn load_from_ace(bytes: &[u8]) -> Result<WasmNuclide, JsValue> {
+        let nuclide = crate::read_nuclide_from_ace_bytes(bytes, None)
+            .map_err(|e| JsValue::from_str(&format!("Failed to read ACE file: {}", e)))?;
+        Ok(WasmNuclide {
+            inner: Arc::new(nuclide),
+        })
+    }
+
+    /// Serialize the nuclide to the binary format.
+    #[wasm_bindgen]
+    pub fn to_binary(&self) -> Result<Vec<u8>, JsValue> {
+        self.inner.to_binary().map_err(|e| JsValue::from_str(&e))
+    }
+
     #[wasm_bindgen]
     pub fn get_name(&self) -> String {
         self.inner
@@ -119,6 +147,40 @@ impl WasmNuclide {
             .unwrap_or_else(|| "Unknown".to_string())
     }
 
+    #[wasm_bindgen]
+    pub fn get_metastable(&self) -> Option<u32> {
+        self.inner.metastable
+    }
+
+    #[wasm_bindgen]
+    pub fn get_atomic_weight_ratio(&self) -> Option<f64> {
+        self.inner.atomic_weight_ratio
+    }
+
+    #[wasm_bindgen]
+    pub fn get_atomic_mass(&self) -> Option<f64> {
+        self.inner.atomic_mass()
+    }
+
+    /// Validate the nuclide data, returning the report as a JavaScript object
+    /// with the nuclide name and a list of issues.
+    #[wasm_bindgen]
+    pub fn validate(&self, tolerance: Option<f64>) -> Result<JsValue, JsValue> {
+        let report = match tolerance {
+            Some(tolerance) => self.inner.validate_with_tolerance(tolerance),
+            None => self.inner.validate(),
+        };
+        let json = report.to_json().map_err(|e| JsValue::from_str(&e))?;
+        js_sys::JSON::parse(&json)
+    }
+
+    #[wasm_bindgen]
+    pub fn doppler_broaden(&mut self, temperature: f64) -> Result<String, JsValue> {
+        Arc::make_mut(&mut self.inner)
+            .doppler_broaden(temperature)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     #[wasm_bindgen]
     pub fn get_available_temperatures(&self) -> Array {
         let temps = if !self.inner.available_temperatures.is_empty() {
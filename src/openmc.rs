@@ -0,0 +1,461 @@
+// Provides conversion of materials to and from the OpenMC materials.xml format
+use crate::material::{FractionType, Material};
+use crate::materials::Materials;
+
+/// Escape a string for use in an XML attribute value.
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Write a single OpenMC `<material>` element with the given ID.
+///
+/// Nuclides are written in alphabetical order with their atom ("ao") or weight
+/// ("wo") fraction. OpenMC does not allow both in one material, so mixed
+/// compositions are converted to atom fractions first. The density is written in the material's own units, and
+/// the name, temperature and volume are added as attributes when set.
+pub fn material_to_openmc_xml_element(material: &Material, id: usize) -> Result<String, String> {
+    if material.nuclides.is_empty() {
+        return Err(format!("Material {} has no nuclides defined", id));
+    }
+
+    let mut attributes = vec![format!("id=\"{}\"", id)];
+    if let Some(name) = material.get_name() {
+        attributes.push(format!("name=\"{}\"", escape_xml(name)));
+    }
+    attributes.push(format!(
+        "temperature=\"{}\"",
+        escape_xml(&material.temperature)
+    ));
+    if let Some(volume) = material.volume {
+        attributes.push(format!("volume=\"{}\"", volume));
+    }
+
+    let mut xml = format!("  <material {}>\n", attributes.join(" "));
+    match material.density {
+        Some(density) => xml.push_str(&format!(
+            "    <density units=\"{}\" value=\"{}\" />\n",
+            escape_xml(&material.density_units),
+            density
+        )),
+        None if material.has_density() => xml.push_str(&format!(
+            "    <density units=\"{}\" />\n",
+            escape_xml(&material.density_units)
+        )),
+        None => return Err(format!("Material {} has no density defined", id)),
+    }
+    for impurity in material.get_impurities() {
+        xml.push_str(&format!("    <!-- impurity {} -->\n", escape_xml(&impurity.to_string())));
+    }
+    let weight_count = material
+        .nuclides
+        .keys()
+        .filter(|n| material.fraction_type_of(n) == FractionType::Weight)
+        .count();
+    let (fraction_type, fractions) = if weight_count == material.nuclides.len() {
+        (FractionType::Weight, material.nuclides.clone())
+    } else if weight_count > 0 {
+        (FractionType::Atom, material.get_atom_fractions())
+    } else {
+        (FractionType::Atom, material.nuclides.clone())
+    };
+    for nuclide in material.get_nuclides() {
+        xml.push_str(&format!(
+            "    <nuclide name=\"{}\" {}=\"{}\" />\n",
+            escape_xml(&nuclide),
+            fraction_type.as_str(),
+            fractions[&nuclide]
+        ));
+    }
+    xml.push_str("  </material>\n");
+    Ok(xml)
+}
+
+/// Write a complete OpenMC `materials.xml` document.
+///
+/// Materials are given IDs 1, 2, 3, ... in the order they are provided so the
+/// same input always produces the same file.
+pub fn materials_to_openmc_xml<'a, I>(materials: I) -> Result<String, String>
+where
+    I: IntoIterator<Item = &'a Material>,
+{
+    let mut xml = String::from("<?xml version='1.0' encoding='utf-8'?>\n<materials>\n");
+    for (index, material) in materials.into_iter().enumerate() {
+        xml.push_str(&material_to_openmc_xml_element(material, index + 1)?);
+    }
+    xml.push_str("</materials>\n");
+    Ok(xml)
+}
+
+/// Read the fraction and fraction type from the `ao` or `wo` attribute of a node.
+fn read_fraction(
+    node: &roxmltree::Node,
+    material_label: &str,
+) -> Result<(f64, FractionType), String> {
+    let tag = node.tag_name().name();
+    let name = node.attribute("name").unwrap_or("");
+    let (value, fraction_type) = match (node.attribute("ao"), node.attribute("wo")) {
+        (Some(value), None) => (value, FractionType::Atom),
+        (None, Some(value)) => (value, FractionType::Weight),
+        _ => {
+            return Err(format!(
+                "{}: <{} name=\"{}\"> must have exactly one of the 'ao' or 'wo' attributes",
+                material_label, tag, name
+            ))
+        }
+    };
+    let what = format!("{} '{}' fraction", tag, name);
+    let fraction = parse_number(value, material_label, &what)?;
+    Ok((fraction, fraction_type))
+}
+
+/// Parse a numeric attribute value, naming the material and quantity on failure.
+fn parse_number(value: &str, material_label: &str, what: &str) -> Result<f64, String> {
+    value.trim().parse::<f64>().map_err(|_| {
+        format!("{}: invalid {} '{}'", material_label, what, value)
+    })
+}
+
+/// Build a [`Material`] from an OpenMC `<material>` node.
+fn material_from_openmc_node(node: &roxmltree::Node) -> Result<Material, String> {
+    let material_label = match (node.attribute("id"), node.attribute("name")) {
+        (Some(id), Some(name)) => format!("Material {} ('{}')", id, name),
+        (Some(id), None) => format!("Material {}", id),
+        (None, Some(name)) => format!("Material '{}'", name),
+        (None, None) => String::from("Material"),
+    };
+
+    let mut material = Material::new();
+    if let Some(name) = node.attribute("name") {
+        material.set_name(name);
+    }
+    if let Some(temperature) = node.attribute("temperature") {
+        material.set_temperature(temperature.trim());
+    }
+    if let Some(volume) = node.attribute("volume") {
+        let volume = parse_number(volume, &material_label, "volume")?;
+        material
+            .volume(Some(volume))
+            .map_err(|e| format!("{}: {}", material_label, e))?;
+    }
+
+    let mut density_found = false;
+    for child in node.children().filter(|n| n.is_element()) {
+        let result = match child.tag_name().name() {
+            "density" => {
+                density_found = true;
+                let units = child.attribute("units").unwrap_or("g/cm3");
+                let value = match child.attribute("value") {
+                    Some(v) => parse_number(v, &material_label, "density")?,
+                    None if units == "sum" => 0.0,
+                    None => {
+                        return Err(format!("{}: <density> has no value", material_label))
+                    }
+                };
+                material.set_density(units, value)
+            }
+            "nuclide" => {
+                let name = child.attribute("name").ok_or_else(|| {
+                    format!("{}: <nuclide> has no name", material_label)
+                })?;
+                let (fraction, fraction_type) = read_fraction(&child, &material_label)?;
+                material.add_nuclide_with_fraction_type(name, fraction, fraction_type)
+            }
+            "element" => {
+                let name = child.attribute("name").ok_or_else(|| {
+                    format!("{}: <element> has no name", material_label)
+                })?;
+                let (fraction, fraction_type) = read_fraction(&child, &material_label)?;
+                match child.attribute("enrichment") {
+                    None => material.add_element_with_fraction_type(name, fraction, fraction_type),
+                    Some(enrichment) => {
+                        let enrichment = parse_number(enrichment, &material_label, "enrichment")?;
+                        // OpenMC defaults to U235 weight percent enrichment for uranium
+                        let target = match (child.attribute("enrichment_target"), name) {
+                            (Some(target), _) => target.to_string(),
+                            (None, "U") => String::from("U235"),
+                            (None, _) => {
+                                return Err(format!(
+                                    "{}: <element name=\"{}\"> has an enrichment but no enrichment_target",
+                                    material_label, name
+                                ))
+                            }
+                        };
+                        let enrichment_type = match child.attribute("enrichment_type") {
+                            Some(t) => t.parse::<FractionType>()?,
+                            None if child.attribute("enrichment_target").is_none() => {
+                                FractionType::Weight
+                            }
+                            None => FractionType::Atom,
+                        };
+                        material.add_element_enriched(
+                            name,
+                            fraction,
+                            fraction_type,
+                            target,
+                            enrichment,
+                            enrichment_type,
+                        )
+                    }
+                }
+            }
+            // Thermal scattering and other transport settings are not material composition
+            "sab" | "isotropic" => Ok(()),
+            other => {
+                return Err(format!(
+                    "{}: unsupported element <{}> in OpenMC material",
+                    material_label, other
+                ))
+            }
+        };
+        result.map_err(|e| format!("{}: {}", material_label, e))?;
+    }
+    if !density_found {
+        return Err(format!("{}: no <density> defined", material_label));
+    }
+    Ok(material)
+}
+
+/// Read the materials of an OpenMC `materials.xml` document.
+///
+/// Supports `<nuclide>` and `<element>` entries with `ao` or `wo` fractions,
+/// element enrichment (`enrichment`, `enrichment_target`, `enrichment_type`),
+/// `<density>` in any of the units accepted by [`Material::set_density`] and
+/// the `name`, `temperature` and `volume` attributes. Materials are added in
+/// document order; `<sab>` entries are ignored.
+pub fn materials_from_openmc_xml(xml: &str) -> Result<Materials, String> {
+    let document = roxmltree::Document::parse(xml)
+        .map_err(|e| format!("Failed to parse OpenMC materials XML: {}", e))?;
+    let root = document.root_element();
+    if root.tag_name().name() != "materials" {
+        return Err(format!(
+            "Expected a <materials> root element in OpenMC materials XML, found <{}>",
+            root.tag_name().name()
+        ));
+    }
+
+    let mut materials = Materials::new();
+    for node in root
+        .children()
+        .filter(|n| n.is_element() && n.tag_name().name() == "material")
+    {
+        materials.append(material_from_openmc_node(&node)?);
+    }
+    Ok(materials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::FractionType;
+
+    #[test]
+    fn test_material_element() {
+        let mut material = Material::new();
+        material.set_name("breeder & multiplier");
+        material.add_nuclide("Li6", 0.6).unwrap();
+        material
+            .add_nuclide_with_fraction_type("Be9", 0.4, FractionType::Weight)
+            .unwrap();
+        material.set_density("g/cm3", 1.5).unwrap();
+        material.volume(Some(10.0)).unwrap();
+
+        let xml = material_to_openmc_xml_element(&material, 3).unwrap();
+        assert_eq!(
+            xml,
+            "  <material id=\"3\" name=\"breeder &amp; multiplier\" temperature=\"294\" volume=\"10\">\n\
+             \x20   <density units=\"g/cm3\" value=\"1.5\" />\n\
+             \x20   <nuclide name=\"Be9\" ao=\"0.4\" />\n\
+             \x20   <nuclide name=\"Li6\" ao=\"0.6\" />\n\
+             \x20 </material>\n"
+        );
+        assert!(!xml.contains("wo="));
+    }
+
+    #[test]
+    fn test_material_element_fraction_types() {
+        let mut material = Material::new();
+        material
+            .add_nuclide_with_fraction_type("Li6", 0.5, FractionType::Weight)
+            .unwrap();
+        material
+            .add_nuclide_with_fraction_type("Li7", 0.5, FractionType::Weight)
+            .unwrap();
+        material.set_density("g/cm3", 0.5).unwrap();
+        let xml = material_to_openmc_xml_element(&material, 1).unwrap();
+        assert!(xml.contains("<nuclide name=\"Li6\" wo=\"0.5\" />"));
+        assert!(xml.contains("<nuclide name=\"Li7\" wo=\"0.5\" />"));
+
+        // Mixing atom and weight fractions is converted to atom fractions only
+        material.add_nuclide("Be9", 0.5).unwrap();
+        let xml = material_to_openmc_xml_element(&material, 1).unwrap();
+        assert!(!xml.contains("wo="));
+        let fractions = material.get_atom_fractions();
+        assert!(xml.contains(&format!("<nuclide name=\"Li6\" ao=\"{}\" />", fractions["Li6"])));
+        assert!(fractions["Li6"] > fractions["Li7"]);
+    }
+
+    #[test]
+    fn test_material_element_density_units() {
+        let mut material = Material::new();
+        material.add_nuclide("Li6", 0.02).unwrap();
+        material.set_density("atom/b-cm", 0.02).unwrap();
+        let xml = material_to_openmc_xml_element(&material, 1).unwrap();
+        assert!(xml.contains("<density units=\"atom/b-cm\" value=\"0.02\" />"));
+
+        material.set_density("sum", 0.0).unwrap();
+        let xml = material_to_openmc_xml_element(&material, 1).unwrap();
+        assert!(xml.contains("<density units=\"sum\" />"));
+    }
+
+    #[test]
+    fn test_material_element_requires_density_and_nuclides() {
+        let mut material = Material::new();
+        assert!(material_to_openmc_xml_element(&material, 1).is_err());
+        material.add_nuclide("Li6", 1.0).unwrap();
+        assert!(material_to_openmc_xml_element(&material, 1).is_err());
+    }
+
+    #[test]
+    fn test_materials_document_ids() {
+        let mut first = Material::new();
+        first.add_nuclide("Li6", 1.0).unwrap();
+        first.set_density("g/cm3", 1.0).unwrap();
+        let mut second = first.clone();
+        second.set_temperature("600");
+
+        let xml = materials_to_openmc_xml([&first, &second]).unwrap();
+        assert!(xml.starts_with("<?xml version='1.0' encoding='utf-8'?>\n<materials>\n"));
+        assert!(xml.ends_with("</materials>\n"));
+        assert!(xml.contains("<material id=\"1\" temperature=\"294\">"));
+        assert!(xml.contains("<material id=\"2\" temperature=\"600\">"));
+        assert_eq!(xml, materials_to_openmc_xml([&first, &second]).unwrap());
+    }
+
+    #[test]
+    fn test_read_openmc_xml() {
+        let xml = r#"<?xml version='1.0' encoding='utf-8'?>
+<materials>
+  <cross_sections>/data/cross_sections.xml</cross_sections>
+  <material depletable="true" id="5" name="fuel" temperature="900" volume="2.5">
+    <density units="g/cc" value="10.4" />
+    <nuclide name="U235" wo="0.04" />
+    <nuclide name="U238" wo="0.96" />
+    <element name="O" ao="2.0" />
+  </material>
+  <material id="6" name="breeder">
+    <density units="atom/b-cm" value="0.05" />
+    <element name="Li" ao="1.0" enrichment="60" enrichment_target="Li6" enrichment_type="ao" />
+    <sab name="c_Graphite" />
+  </material>
+  <material id="7">
+    <density units="sum" />
+    <nuclide name="H1" ao="0.06" />
+  </material>
+</materials>"#;
+        let materials = materials_from_openmc_xml(xml).unwrap();
+        assert_eq!(materials.len(), 3);
+
+        let fuel = materials.get(0).unwrap();
+        assert_eq!(fuel.get_name(), Some("fuel"));
+        assert_eq!(fuel.temperature, "900");
+        assert_eq!(fuel.volume, Some(2.5));
+        assert_eq!(fuel.density, Some(10.4));
+        assert_eq!(fuel.density_units, "g/cc");
+        assert_eq!(fuel.nuclides["U235"], 0.04);
+        assert_eq!(fuel.fraction_type_of("U235"), FractionType::Weight);
+        assert!((fuel.nuclides["O16"] - 2.0 * 0.9976206).abs() < 1e-12);
+
+        let breeder = materials.get(1).unwrap();
+        assert_eq!(breeder.temperature, "294");
+        assert!((breeder.nuclides["Li6"] - 0.6).abs() < 1e-12);
+        assert!((breeder.get_atoms_per_barn_cm()["Li7"] - 0.02).abs() < 1e-12);
+
+        let sum = materials.get(2).unwrap();
+        assert_eq!(sum.density_units, "sum");
+        assert_eq!(sum.get_atoms_per_barn_cm()["H1"], 0.06);
+    }
+
+    #[test]
+    fn test_material_element_impurity_comments() {
+        let mut material = Material::new();
+        material.add_element("Fe", 1.0).unwrap();
+        material
+            .add_impurity("Co", 50.0, FractionType::Weight)
+            .unwrap();
+        material.set_density("g/cm3", 7.8).unwrap();
+        let xml = material.to_openmc_xml().unwrap();
+        assert!(xml.contains("    <!-- impurity Co 50 wppm -->\n"));
+        // Comments are skipped when reading the file back
+        let materials = materials_from_openmc_xml(&xml).unwrap();
+        assert_eq!(materials.get(0).unwrap().nuclides, material.nuclides);
+    }
+
+    #[test]
+    fn test_openmc_xml_round_trip() {
+        let mut material = Material::new();
+        material.set_name("steel");
+        material
+            .add_element_with_fraction_type("Fe", 0.98, FractionType::Weight)
+            .unwrap();
+        material
+            .add_element_with_fraction_type("C", 0.02, FractionType::Weight)
+            .unwrap();
+        material.set_density("g/cm3", 7.8).unwrap();
+        material.set_temperature("600");
+        let xml = material.to_openmc_xml().unwrap();
+
+        let materials = materials_from_openmc_xml(&xml).unwrap();
+        let restored = materials.get(0).unwrap();
+        assert_eq!(restored.nuclides, material.nuclides);
+        assert_eq!(restored.fraction_types, material.fraction_types);
+        assert_eq!(restored.temperature, "600");
+        assert_eq!(materials.to_openmc_xml().unwrap(), xml);
+    }
+
+    #[test]
+    fn test_read_openmc_xml_uranium_enrichment_default() {
+        let xml = r#"<materials><material id="1">
+            <density units="g/cm3" value="19.1" />
+            <element name="U" ao="1.0" enrichment="5.0" />
+        </material></materials>"#;
+        let materials = materials_from_openmc_xml(xml).unwrap();
+        let uranium = materials.get(0).unwrap();
+        let m235 = crate::data::ATOMIC_MASSES["U235"];
+        let mass235 = uranium.nuclides["U235"] * m235;
+        let total_mass: f64 = uranium
+            .nuclides
+            .iter()
+            .map(|(n, f)| f * crate::data::ATOMIC_MASSES[n.as_str()])
+            .sum();
+        assert!((mass235 / total_mass - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_read_openmc_xml_errors() {
+        assert!(materials_from_openmc_xml("<materials><material>").is_err());
+        assert!(materials_from_openmc_xml("<geometry/>").is_err());
+        let no_density = r#"<materials><material id="1"><nuclide name="H1" ao="1"/></material></materials>"#;
+        assert!(materials_from_openmc_xml(no_density)
+            .unwrap_err()
+            .contains("no <density>"));
+        let both = r#"<materials><material id="1"><density units="g/cm3" value="1"/><nuclide name="H1" ao="1" wo="1"/></material></materials>"#;
+        assert!(materials_from_openmc_xml(both).is_err());
+        let macroscopic = r#"<materials><material id="1"><density units="macro" value="1"/><macroscopic name="UO2"/></material></materials>"#;
+        assert!(materials_from_openmc_xml(macroscopic).is_err());
+        let enrichment = r#"<materials><material id="1"><density units="g/cm3" value="1"/><element name="Li" ao="1" enrichment="50"/></material></materials>"#;
+        assert!(materials_from_openmc_xml(enrichment)
+            .unwrap_err()
+            .contains("no enrichment_target"));
+    }
+}
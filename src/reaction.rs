@@ -1,3 +1,4 @@
+use crate::interpolation::{check_interpolation_regions, interpolate_tabulated, interval_law, InterpolationLaw};
 use serde::{Deserialize, Serialize};
 
 /// Represents a single reaction channel (identified by ENDF/MT number) for a
@@ -7,45 +8,106 @@ use serde::{Deserialize, Serialize};
 /// the parent nuclide's top‑level temperature grid (offset by `threshold_idx`).
 /// `cross_section` values correspond 1‑to‑1 with the reaction's effective
 /// energy grid (either its own `energy` or a slice of the parent grid).
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Reaction {
     /// Cross section values in barns for the reaction energy grid.
     pub cross_section: Vec<f64>,
     /// Index into the parent (top‑level) energy grid where this reaction becomes active.
     pub threshold_idx: usize,
-    /// Interpolation flags (currently informational; retained from source data).
+    /// ENDF interpolation law code of each interpolation region (1 histogram,
+    /// 2 lin-lin, 3 lin-log, 4 log-lin, 5 log-log). Empty means lin-lin.
     pub interpolation: Vec<i32>,
+    /// ENDF interpolation breakpoints: the 1-based index of the last point of
+    /// each region within `cross_section`. Empty when a single law applies.
+    #[serde(default)]
+    pub breakpoints: Vec<usize>,
     /// Reaction‑specific energy grid (may be empty until synthesized).
     #[serde(skip, default)]
     pub energy: Vec<f64>, // Reaction-specific energy grid
     /// ENDF/MT reaction identifier.
     pub mt_number: i32, // The MT number for this reaction
+    /// True when the reaction was reconstructed by summing its component
+    /// reactions rather than read from the data (see
+    /// [`crate::Nuclide::reconstruct_redundant_reactions`]).
+    #[serde(default)]
+    pub derived: bool,
 }
 
 impl Reaction {
-    /// Returns the cross section value for a given neutron energy.
+    /// Doppler broaden this reaction by `delta_temperature` K for a target with
+    /// atomic weight ratio `awr`, returning a reaction on the same energy grid.
+    ///
+    /// The cross section is treated as lin-lin pointwise data and broadened
+    /// with the SIGMA1 free gas kernel, so the result is lin-lin. Data starting
+    /// at the bottom of the nuclide energy grid (`threshold_idx` 0) is extended
+    /// as 1/v below it.
+    pub fn doppler_broaden(&self, awr: f64, delta_temperature: f64) -> Result<Reaction, String> {
+        if awr <= 0.0 || !awr.is_finite() {
+            return Err(format!("Atomic weight ratio must be positive, got {}", awr));
+        }
+        if delta_temperature < 0.0 || !delta_temperature.is_finite() {
+            return Err(format!(
+                "Cannot Doppler broaden by {} K, the temperature change must not be negative",
+                delta_temperature
+            ));
+        }
+        if self.energy.len() != self.cross_section.len() {
+            return Err(format!(
+                "MT {} has {} energies but {} cross section values",
+                self.mt_number,
+                self.energy.len(),
+                self.cross_section.len()
+            ));
+        }
+        Ok(Reaction {
+            cross_section: crate::doppler::broaden(
+                &self.energy,
+                &self.cross_section,
+                awr,
+                delta_temperature,
+                self.threshold_idx == 0,
+            ),
+            interpolation: vec![InterpolationLaw::LinLin.endf_code()],
+            breakpoints: Vec::new(),
+            ..self.clone()
+        })
+    }
+
+    /// Check that the interpolation regions are consistent with the cross
+    /// section table and use known ENDF laws.
+    pub fn check_interpolation(&self) -> Result<(), String> {
+        check_interpolation_regions(&self.breakpoints, &self.interpolation, self.cross_section.len())
+            .map_err(|e| format!("MT {}: {}", self.mt_number, e))
+    }
+
+    /// Interpolation law between points `interval` and `interval + 1` of the
+    /// cross section table.
+    pub fn interpolation_law(&self, interval: usize) -> InterpolationLaw {
+        interval_law(&self.breakpoints, &self.interpolation, interval)
+    }
+
+    /// Cross section at `energy` on `energy_grid`, the reaction's energy grid
+    /// (the same length as `cross_section`), following the interpolation laws.
+    /// Energies outside the grid give the first or last value.
+    pub fn cross_section_on_grid(&self, energy_grid: &[f64], energy: f64) -> f64 {
+        interpolate_tabulated(
+            energy_grid,
+            &self.cross_section,
+            &self.breakpoints,
+            &self.interpolation,
+            energy,
+        )
+    }
+
+    /// Returns the cross section value for a given neutron energy, interpolated
+    /// with the reaction's interpolation laws.
     /// If the energy is below the grid, returns the first value.
     /// If above, returns the last value.
-    /// Otherwise, returns the value at the closest lower index.
     pub fn cross_section_at(&self, energy: f64) -> Option<f64> {
         if self.energy.is_empty() || self.cross_section.is_empty() {
             return None;
         }
-        match self
-            .energy
-            .binary_search_by(|e| e.partial_cmp(&energy).unwrap())
-        {
-            Ok(idx) => self.cross_section.get(idx).copied(),
-            Err(idx) => {
-                if idx == 0 {
-                    self.cross_section.get(0).copied()
-                } else if idx >= self.cross_section.len() {
-                    self.cross_section.last().copied()
-                } else {
-                    self.cross_section.get(idx - 1).copied()
-                }
-            }
-        }
+        Some(self.cross_section_on_grid(&self.energy, energy))
     }
 }
 #[cfg(test)]
@@ -57,18 +119,68 @@ mod tests {
         let reaction = Reaction {
             cross_section: vec![1.0, 2.0, 3.0, 4.0],
             threshold_idx: 0,
-            interpolation: vec![],
+            interpolation: vec![1],
+            breakpoints: vec![],
             energy: vec![0.5, 1.0, 2.0, 5.0],
             mt_number: 102,
+            derived: false,
         };
 
         // Below grid
         assert_eq!(reaction.cross_section_at(0.1), Some(1.0));
         // Exact match
         assert_eq!(reaction.cross_section_at(1.0), Some(2.0));
-        // Between grid points
+        // Between grid points (histogram)
         assert_eq!(reaction.cross_section_at(1.5), Some(2.0));
         // Above grid
         assert_eq!(reaction.cross_section_at(10.0), Some(4.0));
     }
+
+    #[test]
+    fn test_cross_section_at_follows_interpolation_regions() {
+        let mut reaction = Reaction {
+            cross_section: vec![1.0, 2.0, 3.0, 4.0],
+            threshold_idx: 0,
+            interpolation: vec![],
+            breakpoints: vec![],
+            energy: vec![0.5, 1.0, 2.0, 5.0],
+            mt_number: 102,
+            derived: false,
+        };
+        // Lin-lin when no law is given
+        assert_eq!(reaction.cross_section_at(1.5), Some(2.5));
+
+        // Histogram up to the second point, log-log after it
+        reaction.interpolation = vec![1, 5];
+        reaction.breakpoints = vec![2, 4];
+        assert!(reaction.check_interpolation().is_ok());
+        assert_eq!(reaction.interpolation_law(0), InterpolationLaw::Histogram);
+        assert_eq!(reaction.interpolation_law(2), InterpolationLaw::LogLog);
+        assert_eq!(reaction.cross_section_at(0.75), Some(1.0));
+        let expected = 2.0 * (1.5f64.ln() * 2f64.sqrt().ln() / 2f64.ln()).exp();
+        assert!((reaction.cross_section_at(2f64.sqrt()).unwrap() - expected).abs() < 1e-12);
+
+        reaction.breakpoints = vec![2, 3];
+        assert!(reaction.check_interpolation().is_err());
+    }
+
+    #[test]
+    fn test_doppler_broaden_keeps_grid() {
+        let reaction = Reaction {
+            cross_section: vec![10.0, 10.0, 10.0],
+            threshold_idx: 0,
+            interpolation: vec![3],
+            breakpoints: vec![],
+            energy: vec![1.0, 2.0, 3.0],
+            mt_number: 2,
+            derived: false,
+        };
+        let broadened = reaction.doppler_broaden(1.0, 300.0).unwrap();
+        assert_eq!(broadened.energy, reaction.energy);
+        assert_eq!(broadened.mt_number, 2);
+        assert_eq!(broadened.interpolation, vec![2]);
+        assert_eq!(reaction.doppler_broaden(1.0, 0.0).unwrap().cross_section, reaction.cross_section);
+        assert!(reaction.doppler_broaden(1.0, -10.0).is_err());
+        assert!(reaction.doppler_broaden(0.0, 300.0).is_err());
+    }
 }
@@ -13,6 +13,7 @@ struct ReactionData {
     threshold_idx: usize,
     cross_section: Vec<f64>,
     interpolation: Vec<i32>,
+    breakpoints: Vec<usize>,
     energy: Vec<f64>,
 }
 
@@ -25,8 +26,10 @@ impl WasmReaction {
                 threshold_idx,
                 cross_section: Vec::new(),
                 interpolation: Vec::new(),
+                breakpoints: Vec::new(),
                 energy: Vec::new(),
                 mt_number: 0, // Default MT number
+                derived: false,
             },
         }
     }
@@ -41,6 +44,11 @@ impl WasmReaction {
         self.inner.interpolation = interpolation;
     }
 
+    #[wasm_bindgen]
+    pub fn set_breakpoints(&mut self, breakpoints: Vec<usize>) {
+        self.inner.breakpoints = breakpoints;
+    }
+
     #[wasm_bindgen]
     pub fn set_energy(&mut self, energy: Vec<f64>) {
         self.inner.energy = energy;
@@ -69,6 +77,25 @@ impl WasmReaction {
             .collect::<Array>()
     }
 
+    #[wasm_bindgen]
+    pub fn get_breakpoints(&self) -> Array {
+        self.inner
+            .breakpoints
+            .iter()
+            .map(|&x| JsValue::from_f64(x as f64))
+            .collect::<Array>()
+    }
+
+    #[wasm_bindgen]
+    pub fn get_cross_section_at(&self, energy: f64) -> Option<f64> {
+        self.inner.cross_section_at(energy)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_derived(&self) -> bool {
+        self.inner.derived
+    }
+
     #[wasm_bindgen]
     pub fn get_energy(&self) -> Array {
         self.inner
@@ -84,6 +111,7 @@ impl WasmReaction {
             threshold_idx: self.inner.threshold_idx,
             cross_section: self.inner.cross_section.clone(),
             interpolation: self.inner.interpolation.clone(),
+            breakpoints: self.inner.breakpoints.clone(),
             energy: self.inner.energy.clone(),
         };
 
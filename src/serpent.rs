@@ -0,0 +1,181 @@
+// Provides conversion of materials to Serpent material (mat) definitions
+use crate::material::{FractionType, Material};
+use crate::mcnp::nuclide_to_zaid;
+use std::collections::HashMap;
+
+/// Name used for a material in Serpent: the material name with whitespace
+/// replaced by underscores, or `m<number>` for unnamed materials.
+fn serpent_name(material: &Material, number: usize) -> String {
+    match material.get_name() {
+        Some(name) if !name.trim().is_empty() => name
+            .trim()
+            .chars()
+            .map(|c| if c.is_whitespace() { '_' } else { c })
+            .collect(),
+        _ => format!("m{}", number),
+    }
+}
+
+/// Write a material as a Serpent `mat` block.
+///
+/// Unnamed materials are called `m<number>`. The density is written as a
+/// negative mass density in g/cm3, a positive atom density in atom/b-cm or the
+/// `sum` keyword, and the volume is added with the `vol` option when set.
+/// Nuclides are written as ZAIDs with `library` (e.g. `"03c"`) as the suffix
+/// when given. Compositions entirely in weight fractions are written as
+/// negative mass fractions, otherwise as positive atom fractions ("sum"
+/// materials are written as atom densities in atom/b-cm).
+pub fn material_to_serpent(
+    material: &Material,
+    number: usize,
+    library: Option<&str>,
+) -> Result<String, String> {
+    let name = serpent_name(material, number);
+    if material.nuclides.is_empty() {
+        return Err(format!("Material '{}' has no nuclides defined", name));
+    }
+
+    let density = match (material.density_units.as_str(), material.density) {
+        ("sum", _) => String::from("sum"),
+        ("atom/b-cm", Some(density)) => format!("{}", density),
+        ("atom/cm3", Some(density)) => format!("{}", density * 1.0e-24),
+        (_, Some(_)) => format!("{}", -material.get_mass_density()?),
+        (_, None) => return Err(format!("Material '{}' has no density defined", name)),
+    };
+
+    let weight_count = material
+        .nuclides
+        .keys()
+        .filter(|n| material.fraction_type_of(n) == FractionType::Weight)
+        .count();
+    let fractions: HashMap<String, f64> = if material.density_units == "sum" {
+        material.get_atoms_per_barn_cm()
+    } else if weight_count == material.nuclides.len() {
+        material.nuclides.iter().map(|(n, &f)| (n.clone(), -f)).collect()
+    } else if weight_count > 0 {
+        material.get_atom_fractions()
+    } else {
+        material.nuclides.clone()
+    };
+
+    let suffix = match library.map(|l| l.trim_start_matches('.')) {
+        Some(l) if !l.is_empty() => format!(".{}", l),
+        _ => String::new(),
+    };
+
+    let mut block = format!("% Material {}, temperature {}\n", name, material.temperature);
+    for impurity in material.get_impurities() {
+        block.push_str(&format!("% impurity {}\n", impurity));
+    }
+    block.push_str(&format!("mat {} {}", name, density));
+    if let Some(volume) = material.volume {
+        block.push_str(&format!(" vol {}", volume));
+    }
+    block.push('\n');
+    for nuclide in material.get_nuclides() {
+        let zaid = format!("{}{}", nuclide_to_zaid(&nuclide)?, suffix);
+        block.push_str(&format!("{:<12} {}\n", zaid, fractions[&nuclide]));
+    }
+    Ok(block)
+}
+
+/// Write materials as Serpent `mat` blocks, numbering unnamed materials
+/// `m1`, `m2`, ... by their position.
+pub fn materials_to_serpent<'a, I>(materials: I, library: Option<&str>) -> Result<String, String>
+where
+    I: IntoIterator<Item = &'a Material>,
+{
+    let mut names: Vec<String> = Vec::new();
+    let mut blocks: Vec<String> = Vec::new();
+    for (index, material) in materials.into_iter().enumerate() {
+        let name = serpent_name(material, index + 1);
+        if names.contains(&name) {
+            return Err(format!(
+                "Material name '{}' is used more than once, Serpent material names must be unique",
+                name
+            ));
+        }
+        blocks.push(material_to_serpent(material, index + 1, library)?);
+        names.push(name);
+    }
+    Ok(blocks.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_material_to_serpent_mass_density() {
+        let mut material = Material::new();
+        material.set_name("light water");
+        material.add_nuclide("H1", 2.0).unwrap();
+        material.add_nuclide("O16", 1.0).unwrap();
+        material.set_density("g/cm3", 1.0).unwrap();
+        material.volume(Some(10.0)).unwrap();
+        assert_eq!(
+            material_to_serpent(&material, 1, Some("03c")).unwrap(),
+            "% Material light_water, temperature 294\n\
+             mat light_water -1 vol 10\n\
+             1001.03c     2\n\
+             8016.03c     1\n"
+        );
+    }
+
+    #[test]
+    fn test_material_to_serpent_weight_fractions_and_atom_density() {
+        let mut material = Material::new();
+        material
+            .add_nuclide_with_fraction_type("Fe56", 0.9, FractionType::Weight)
+            .unwrap();
+        material
+            .add_nuclide_with_fraction_type("Cr52", 0.1, FractionType::Weight)
+            .unwrap();
+        material.set_density("atom/b-cm", 0.08).unwrap();
+        let block = material_to_serpent(&material, 4, None).unwrap();
+        assert!(block.contains("mat m4 0.08\n"));
+        assert!(block.contains("24052        -0.1\n"));
+        assert!(block.contains("26056        -0.9\n"));
+    }
+
+    #[test]
+    fn test_material_to_serpent_sum_density() {
+        let mut material = Material::new();
+        material.set_name("gas");
+        material.add_nuclide("He4", 2.5e-5).unwrap();
+        material.set_density("sum", 0.0).unwrap();
+        let block = material_to_serpent(&material, 1, None).unwrap();
+        assert!(block.contains("mat gas sum\n"));
+        assert!(block.contains("2004         0.000025\n"));
+    }
+
+    #[test]
+    fn test_material_to_serpent_impurity_comments() {
+        let mut material = Material::new();
+        material.set_name("steel");
+        material.add_element("Fe", 1.0).unwrap();
+        material.add_impurity("Nb", 10.0, FractionType::Weight).unwrap();
+        material.set_density("g/cm3", 7.8).unwrap();
+        let block = material_to_serpent(&material, 1, None).unwrap();
+        assert!(block.starts_with(
+            "% Material steel, temperature 294\n% impurity Nb 10 wppm\nmat steel -7.8\n"
+        ));
+    }
+
+    #[test]
+    fn test_materials_to_serpent() {
+        let mut first = Material::new();
+        first.add_element("Li", 1.0).unwrap();
+        first.set_density("g/cm3", 0.534).unwrap();
+        let mut second = first.clone();
+        second.set_name("lithium");
+        let text = materials_to_serpent([&first, &second], Some("03c")).unwrap();
+        assert!(text.contains("mat m1 -0.534\n"));
+        assert!(text.contains("mat lithium -0.534\n"));
+
+        let mut duplicate = second.clone();
+        duplicate.set_name("lithium");
+        assert!(materials_to_serpent([&second, &duplicate], None).is_err());
+        assert!(material_to_serpent(&Material::new(), 1, None).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+// Provides numeric handling of the temperatures nuclear data are evaluated at
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How cross sections are evaluated at a temperature that falls between the
+/// temperatures present in the nuclear data.
+///
+/// * `Nearest` uses the data at the closest available temperature.
+/// * `Interpolation` linearly interpolates the cross sections in temperature
+///   between the two bracketing temperatures.
+/// * `Stochastic` picks one of the two bracketing temperatures at random for
+///   each sampled lookup, with probabilities equal to the interpolation
+///   weights. Tabulated cross sections are the expected values, which match
+///   linear interpolation.
+/// * `Broadening` Doppler broadens the 0 K data, or the lowest temperature
+///   data, to the requested temperature (see [`crate::Nuclide::doppler_broaden`]).
+///
+/// Temperatures below or above the available range use the closest available
+/// temperature for the other methods. 0 K data is not chosen by the other
+/// methods while data at other temperatures is available, it is only the
+/// starting point for `Broadening`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureMethod {
+    #[default]
+    Nearest,
+    Interpolation,
+    Stochastic,
+    Broadening,
+}
+
+impl TemperatureMethod {
+    /// Data temperatures combined to tabulate cross sections at `temperature`
+    /// (K) and their weights, chosen from the `available` temperature keys
+    /// (e.g. `"294"`, `"600K"`).
+    ///
+    /// The weights sum to one. `Stochastic` gives the interpolation weights,
+    /// so tabulated cross sections are its expected values; the random choice
+    /// is made per lookup by [`TemperatureMethod::select`].
+    pub fn weights(
+        &self,
+        available: &[String],
+        temperature: f64,
+    ) -> Result<Vec<(String, f64)>, String> {
+        let bracket = bracketing_temperatures(available, temperature)?;
+        Ok(match self {
+            TemperatureMethod::Interpolation | TemperatureMethod::Stochastic => bracket,
+            // Data not yet broadened uses the nearest
+            TemperatureMethod::Nearest | TemperatureMethod::Broadening => nearest(bracket),
+        })
+    }
+
+    /// Data temperatures used for a single lookup at `temperature` (K) and
+    /// their weights, drawing the `Stochastic` choice from `rng`.
+    ///
+    /// The weights sum to one. Only `Interpolation` can return two entries.
+    pub fn select<R: rand::Rng + ?Sized>(
+        &self,
+        available: &[String],
+        temperature: f64,
+        rng: &mut R,
+    ) -> Result<Vec<(String, f64)>, String> {
+        if *self != TemperatureMethod::Stochastic {
+            return self.weights(available, temperature);
+        }
+        let bracket = bracketing_temperatures(available, temperature)?;
+        Ok(vec![(choose_temperature(&bracket, rng).to_string(), 1.0)])
+    }
+
+    /// Temperature keys that must be loaded to evaluate cross sections at
+    /// `temperature` (K): the nearest one, both bracketing temperatures for
+    /// the interpolating methods, or the temperature to broaden from.
+    pub fn required_temperatures(
+        &self,
+        available: &[String],
+        temperature: f64,
+    ) -> Result<Vec<String>, String> {
+        let bracket = bracketing_temperatures(available, temperature)?;
+        let bracket = match self {
+            TemperatureMethod::Broadening => {
+                if bracket.len() == 1 && parse_temperature(&bracket[0].0) == Some(temperature) {
+                    bracket
+                } else {
+                    vec![(broadening_base_temperature(available)?, 1.0)]
+                }
+            }
+            TemperatureMethod::Nearest => nearest(bracket),
+            _ => bracket,
+        };
+        Ok(bracket.into_iter().map(|(key, _)| key).collect())
+    }
+}
+
+/// The closer of two bracketing temperatures with weight one, ties going to
+/// the lower temperature.
+fn nearest(mut bracket: Vec<(String, f64)>) -> Vec<(String, f64)> {
+    if bracket.len() == 1 {
+        return bracket;
+    }
+    let chosen = usize::from(bracket[1].1 > bracket[0].1);
+    let (key, _) = bracket.swap_remove(chosen);
+    vec![(key, 1.0)]
+}
+
+/// One of the weighted temperatures from [`TemperatureMethod::weights`],
+/// drawn with its weight as the probability. A single temperature is
+/// returned without drawing from `rng`.
+pub(crate) fn choose_temperature<'a, R: rand::Rng + ?Sized>(
+    weights: &'a [(String, f64)],
+    rng: &mut R,
+) -> &'a str {
+    let chosen = if weights.len() == 1 {
+        0
+    } else {
+        usize::from(rng.gen::<f64>() >= weights[0].1)
+    };
+    &weights[chosen].0
+}
+
+impl fmt::Display for TemperatureMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TemperatureMethod::Nearest => "nearest",
+            TemperatureMethod::Interpolation => "interpolation",
+            TemperatureMethod::Stochastic => "stochastic",
+            TemperatureMethod::Broadening => "broadening",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TemperatureMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "nearest" => Ok(TemperatureMethod::Nearest),
+            "interpolation" | "linear" => Ok(TemperatureMethod::Interpolation),
+            "stochastic" => Ok(TemperatureMethod::Stochastic),
+            "broadening" | "doppler" => Ok(TemperatureMethod::Broadening),
+            _ => Err(format!(
+                "Unknown temperature method '{}', expected 'nearest', 'interpolation', 'stochastic' or 'broadening'",
+                s
+            )),
+        }
+    }
+}
+
+/// Temperature in K of a temperature key such as `"294"`, `"293.6"` or `"600K"`.
+pub fn parse_temperature(key: &str) -> Option<f64> {
+    let trimmed = key.trim();
+    let number = trimmed
+        .strip_suffix('K')
+        .or_else(|| trimmed.strip_suffix('k'))
+        .unwrap_or(trimmed);
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|t| t.is_finite() && *t >= 0.0)
+}
+
+/// Temperature key Doppler broadening starts from: 0 K when available,
+/// otherwise the lowest available temperature.
+pub fn broadening_base_temperature(available: &[String]) -> Result<String, String> {
+    available
+        .iter()
+        .filter_map(|key| parse_temperature(key).map(|t| (t, key)))
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+        .map(|(_, key)| key.clone())
+        .ok_or_else(|| {
+            format!(
+                "No numeric temperatures to Doppler broaden from, available temperatures: [{}]",
+                available.join(", ")
+            )
+        })
+}
+
+/// Temperature keys loaded when none are requested: all of `available`
+/// except 0 K, which is only needed to Doppler broaden from (see
+/// [`broadening_base_temperature`]), unless there is no other temperature.
+pub fn default_temperatures(available: &[String]) -> Vec<String> {
+    let above_zero: Vec<String> = available
+        .iter()
+        .filter(|key| parse_temperature(key) != Some(0.0))
+        .cloned()
+        .collect();
+    if above_zero.is_empty() {
+        available.to_vec()
+    } else {
+        above_zero
+    }
+}
+
+/// Available temperature keys bracketing `temperature` (K) with their linear
+/// interpolation weights.
+///
+/// A temperature equal to an available one, or outside the available range,
+/// gives the single closest key with weight one. Keys that are not numbers are
+/// ignored, as is 0 K unless it is the only temperature (see
+/// [`default_temperatures`]).
+pub fn bracketing_temperatures(
+    available: &[String],
+    temperature: f64,
+) -> Result<Vec<(String, f64)>, String> {
+    if !temperature.is_finite() || temperature < 0.0 {
+        return Err(format!("Invalid temperature {} K", temperature));
+    }
+    let candidates = default_temperatures(available);
+    let mut temps: Vec<(f64, &String)> = candidates
+        .iter()
+        .filter_map(|key| parse_temperature(key).map(|t| (t, key)))
+        .collect();
+    if temps.is_empty() {
+        return Err(format!(
+            "No numeric temperatures available to evaluate {} K, available temperatures: [{}]",
+            temperature,
+            available.join(", ")
+        ));
+    }
+    temps.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let upper = temps.partition_point(|(t, _)| *t < temperature);
+    if upper == temps.len() {
+        return Ok(vec![(temps[upper - 1].1.clone(), 1.0)]);
+    }
+    let (t_high, key_high) = temps[upper];
+    if upper == 0 || t_high == temperature {
+        return Ok(vec![(key_high.clone(), 1.0)]);
+    }
+    let (t_low, key_low) = temps[upper - 1];
+    let f = (temperature - t_low) / (t_high - t_low);
+    Ok(vec![(key_low.clone(), 1.0 - f), (key_high.clone(), f)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn keys(temps: &[&str]) -> Vec<String> {
+        temps.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_temperature() {
+        assert_eq!(parse_temperature("294"), Some(294.0));
+        assert_eq!(parse_temperature("293.6"), Some(293.6));
+        assert_eq!(parse_temperature("600K"), Some(600.0));
+        assert_eq!(parse_temperature("room"), None);
+        assert_eq!(parse_temperature("-5"), None);
+    }
+
+    #[test]
+    fn test_bracketing_temperatures() {
+        let available = keys(&["900", "294", "600K"]);
+        let bracket = bracketing_temperatures(&available, 450.0).unwrap();
+        assert_eq!(bracket[0].0, "294");
+        assert_eq!(bracket[1].0, "600K");
+        assert!((bracket[0].1 - 150.0 / 306.0).abs() < 1e-12);
+        assert!((bracket[0].1 + bracket[1].1 - 1.0).abs() < 1e-12);
+
+        assert_eq!(bracketing_temperatures(&available, 600.0).unwrap(), vec![("600K".to_string(), 1.0)]);
+        assert_eq!(bracketing_temperatures(&available, 10.0).unwrap(), vec![("294".to_string(), 1.0)]);
+        assert_eq!(bracketing_temperatures(&available, 2500.0).unwrap(), vec![("900".to_string(), 1.0)]);
+        assert!(bracketing_temperatures(&keys(&["room"]), 300.0).is_err());
+    }
+
+    #[test]
+    fn test_select_by_method() {
+        let available = keys(&["294", "900"]);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let nearest = TemperatureMethod::Nearest.select(&available, 700.0, &mut rng).unwrap();
+        assert_eq!(nearest, vec![("900".to_string(), 1.0)]);
+
+        let interpolated = TemperatureMethod::Interpolation
+            .select(&available, 700.0, &mut rng)
+            .unwrap();
+        assert_eq!(interpolated.len(), 2);
+        assert_eq!(
+            TemperatureMethod::Stochastic.weights(&available, 700.0).unwrap(),
+            TemperatureMethod::Interpolation.weights(&available, 700.0).unwrap()
+        );
+        assert_eq!(
+            TemperatureMethod::Nearest.weights(&available, 700.0).unwrap(),
+            nearest
+        );
+
+        // The stochastic choice is reproducible with a seeded generator
+        let draws = |seed| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| TemperatureMethod::Stochastic.select(&available, 500.0, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(draws(7), draws(7));
+
+        let high = (0..1000)
+            .map(|_| TemperatureMethod::Stochastic.select(&available, 750.0, &mut rng).unwrap())
+            .filter(|s| s[0].0 == "900")
+            .count();
+        let expected = 1000.0 * (750.0 - 294.0) / (900.0 - 294.0);
+        assert!((high as f64 - expected).abs() < 60.0, "chose 900 K {} times", high);
+
+        assert_eq!(
+            TemperatureMethod::Interpolation.required_temperatures(&available, 500.0).unwrap(),
+            available
+        );
+        assert_eq!(
+            TemperatureMethod::Nearest.required_temperatures(&available, 500.0).unwrap(),
+            vec!["294".to_string()]
+        );
+        let with_zero = keys(&["900", "0", "294"]);
+        assert_eq!(
+            TemperatureMethod::Broadening.required_temperatures(&with_zero, 1200.0).unwrap(),
+            vec!["0".to_string()]
+        );
+        // Other methods only use 0 K data when there is nothing else
+        assert_eq!(
+            TemperatureMethod::Nearest.required_temperatures(&with_zero, 50.0).unwrap(),
+            vec!["294".to_string()]
+        );
+        assert_eq!(
+            TemperatureMethod::Interpolation.weights(&with_zero, 100.0).unwrap(),
+            vec![("294".to_string(), 1.0)]
+        );
+        assert_eq!(
+            TemperatureMethod::Nearest.required_temperatures(&keys(&["0"]), 300.0).unwrap(),
+            vec!["0".to_string()]
+        );
+        assert_eq!(
+            TemperatureMethod::Broadening.required_temperatures(&available, 600.0).unwrap(),
+            vec!["294".to_string()]
+        );
+        assert_eq!(
+            TemperatureMethod::Broadening.required_temperatures(&available, 900.0).unwrap(),
+            vec!["900".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_method_from_str() {
+        assert_eq!("Nearest".parse::<TemperatureMethod>().unwrap(), TemperatureMethod::Nearest);
+        assert_eq!("interpolation".parse::<TemperatureMethod>().unwrap(), TemperatureMethod::Interpolation);
+        assert_eq!("stochastic".parse::<TemperatureMethod>().unwrap(), TemperatureMethod::Stochastic);
+        assert_eq!("Doppler".parse::<TemperatureMethod>().unwrap(), TemperatureMethod::Broadening);
+        assert!("cubic".parse::<TemperatureMethod>().is_err());
+        assert_eq!(TemperatureMethod::Interpolation.to_string(), "interpolation");
+    }
+}
@@ -0,0 +1,434 @@
+// Provides consistency checks of nuclear data with a structured report of the problems found
+use crate::nuclide::{sum_on_grid, Nuclide};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Default relative tolerance used when comparing MT 1 with the sum of its
+/// partial reactions.
+pub const DEFAULT_SUM_TOLERANCE: f64 = 1e-6;
+
+/// Kind of problem found in nuclear data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationIssueKind {
+    /// An energy grid decreases between two points.
+    NonMonotonicEnergy,
+    /// An energy grid repeats a point.
+    DuplicateEnergy,
+    /// A reaction has negative cross section values.
+    NegativeCrossSection,
+    /// A reaction's `threshold_idx` is not inside the energy grid.
+    ThresholdBeyondGrid,
+    /// A reaction has a different number of cross section values than energies.
+    LengthMismatch,
+    /// MT 1 differs from the sum of its partial reactions.
+    TotalMismatch,
+    /// MT 1 is missing, or cannot be checked, because MT 2 or MT 3 is not
+    /// available to sum.
+    IncompleteTotal,
+    /// A declared temperature has no reaction data or energy grid.
+    MissingTemperature,
+}
+
+impl ValidationIssueKind {
+    /// Name of the kind, as used in the JSON and Python representations.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationIssueKind::NonMonotonicEnergy => "non_monotonic_energy",
+            ValidationIssueKind::DuplicateEnergy => "duplicate_energy",
+            ValidationIssueKind::NegativeCrossSection => "negative_cross_section",
+            ValidationIssueKind::ThresholdBeyondGrid => "threshold_beyond_grid",
+            ValidationIssueKind::LengthMismatch => "length_mismatch",
+            ValidationIssueKind::TotalMismatch => "total_mismatch",
+            ValidationIssueKind::IncompleteTotal => "incomplete_total",
+            ValidationIssueKind::MissingTemperature => "missing_temperature",
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single problem found in nuclear data, located by temperature, MT number
+/// and the first offending index where these apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub kind: ValidationIssueKind,
+    pub temperature: Option<String>,
+    pub mt: Option<i32>,
+    /// First offending index into the energy grid or cross section values.
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.kind)?;
+        if let Some(temperature) = &self.temperature {
+            write!(f, " temperature {}", temperature)?;
+        }
+        if let Some(mt) = self.mt {
+            write!(f, " MT {}", mt)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Result of validating a nuclide's data (see [`Nuclide::validate`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Name of the validated nuclide, when known.
+    pub nuclide: Option<String>,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// True when no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues of the given kind.
+    pub fn issues_of_kind(&self, kind: ValidationIssueKind) -> Vec<&ValidationIssue> {
+        self.issues.iter().filter(|issue| issue.kind == kind).collect()
+    }
+
+    /// Serialize the report to a JSON string.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    fn push(
+        &mut self,
+        kind: ValidationIssueKind,
+        temperature: Option<&str>,
+        mt: Option<i32>,
+        index: Option<usize>,
+        message: String,
+    ) {
+        self.issues.push(ValidationIssue {
+            kind,
+            temperature: temperature.map(str::to_string),
+            mt,
+            index,
+            message,
+        });
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.nuclide.as_deref().unwrap_or("unknown nuclide");
+        if self.is_valid() {
+            return write!(f, "{}: no issues found", name);
+        }
+        write!(f, "{}: {} issue(s) found", name, self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check a nuclide's data for consistency, comparing MT 1 with the sum of its
+/// partials to within the relative `tolerance`.
+pub(crate) fn validate_nuclide(nuclide: &Nuclide, tolerance: f64) -> ValidationReport {
+    use ValidationIssueKind::*;
+    let mut report = ValidationReport {
+        nuclide: nuclide.name.clone(),
+        issues: Vec::new(),
+    };
+
+    let mut temperatures: Vec<&String> = nuclide.reactions.keys().collect();
+    if let Some(energy_map) = &nuclide.energy {
+        temperatures.extend(energy_map.keys().filter(|t| !nuclide.reactions.contains_key(*t)));
+    }
+    temperatures.sort();
+
+    // Declared temperatures without data
+    for temperature in &nuclide.temperatures_without_data {
+        report.push(
+            MissingTemperature,
+            Some(temperature),
+            None,
+            None,
+            "listed in the temperatures but the data has no reactions or energy grid for it".to_string(),
+        );
+    }
+    for temperature in &nuclide.loaded_temperatures {
+        if !nuclide.reactions.contains_key(temperature) {
+            report.push(
+                MissingTemperature,
+                Some(temperature),
+                None,
+                None,
+                "listed as loaded but has no reaction data".to_string(),
+            );
+        }
+    }
+
+    for temperature in temperatures {
+        let grid = nuclide.energy.as_ref().and_then(|e| e.get(temperature));
+        match grid {
+            Some(grid) => check_energy_grid(&mut report, temperature, grid),
+            None => report.push(
+                MissingTemperature,
+                Some(temperature),
+                None,
+                None,
+                "has reaction data but no energy grid".to_string(),
+            ),
+        }
+        let reactions = match nuclide.reactions.get(temperature) {
+            Some(reactions) => reactions,
+            None => {
+                report.push(
+                    MissingTemperature,
+                    Some(temperature),
+                    None,
+                    None,
+                    "has an energy grid but no reaction data".to_string(),
+                );
+                continue;
+            }
+        };
+
+        let mut mts: Vec<&i32> = reactions.keys().collect();
+        mts.sort();
+        for &mt in mts {
+            let reaction = &reactions[&mt];
+            // Reconstructed sums only repeat problems of their components
+            if reaction.derived {
+                continue;
+            }
+            let xs = &reaction.cross_section;
+            let grid_points = grid.map(|grid| grid.len());
+            if let Some(points) = grid_points.filter(|&points| reaction.threshold_idx >= points) {
+                report.push(
+                    ThresholdBeyondGrid,
+                    Some(temperature),
+                    Some(mt),
+                    Some(reaction.threshold_idx),
+                    format!(
+                        "threshold_idx {} is beyond the energy grid of {} points",
+                        reaction.threshold_idx, points
+                    ),
+                );
+            } else {
+                // Reactions may carry their own energy grid, otherwise they use
+                // the nuclide grid from threshold_idx
+                let energies = if reaction.energy.is_empty() {
+                    grid_points.map(|points| points - reaction.threshold_idx)
+                } else {
+                    Some(reaction.energy.len())
+                };
+                if let Some(energies) = energies.filter(|&n| n != xs.len()) {
+                    report.push(
+                        LengthMismatch,
+                        Some(temperature),
+                        Some(mt),
+                        None,
+                        format!("{} cross section values but {} energies", xs.len(), energies),
+                    );
+                }
+            }
+            let negative: Vec<usize> = (0..xs.len()).filter(|&i| xs[i] < 0.0).collect();
+            if let Some(&first) = negative.first() {
+                let lowest = negative.iter().map(|&i| xs[i]).fold(f64::INFINITY, f64::min);
+                report.push(
+                    NegativeCrossSection,
+                    Some(temperature),
+                    Some(mt),
+                    Some(first),
+                    format!("{} negative values, lowest {} b", negative.len(), lowest),
+                );
+            }
+        }
+
+        // MT 1 against the sum of its partials, which are all needed
+        let missing: Vec<String> = crate::data::SUM_RULES[&1]
+            .iter()
+            .filter(|mt| !reactions.contains_key(mt))
+            .map(|mt| mt.to_string())
+            .collect();
+        if !missing.is_empty() {
+            let message = if reactions.contains_key(&1) {
+                "cannot be checked against its partials"
+            } else {
+                "is missing and cannot be summed from its partials"
+            };
+            report.push(
+                IncompleteTotal,
+                Some(temperature),
+                Some(1),
+                None,
+                format!("{}, MT {} not available", message, missing.join(" and ")),
+            );
+            continue;
+        }
+        if let (Some(grid), Some(total)) = (grid, reactions.get(&1)) {
+            let partials: Vec<_> = crate::data::SUM_RULES[&1].iter().map(|mt| &reactions[mt]).collect();
+            if total.derived {
+                continue;
+            }
+            if let (Some((_, total)), Some((_, sum))) = (sum_on_grid(&[total], grid), sum_on_grid(&partials, grid)) {
+                let mismatches: Vec<usize> = (0..grid.len())
+                    .filter(|&i| (total[i] - sum[i]).abs() > tolerance * total[i].abs().max(sum[i].abs()))
+                    .collect();
+                if let Some(&first) = mismatches.first() {
+                    let largest = mismatches
+                        .iter()
+                        .map(|&i| (total[i] - sum[i]).abs() / total[i].abs().max(sum[i].abs()))
+                        .fold(0.0, f64::max);
+                    report.push(
+                        TotalMismatch,
+                        Some(temperature),
+                        Some(1),
+                        Some(first),
+                        format!(
+                            "differs from the sum of MT {} at {} energies, by up to {:.3e} relative (tolerance {:.1e}), first at {} eV",
+                            partials.iter().map(|r| r.mt_number.to_string()).collect::<Vec<_>>().join(" + "),
+                            mismatches.len(),
+                            largest,
+                            tolerance,
+                            grid[first]
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    report
+}
+
+/// Report decreasing and repeated points of an energy grid.
+fn check_energy_grid(report: &mut ValidationReport, temperature: &str, grid: &[f64]) {
+    let decreasing: Vec<usize> = (1..grid.len()).filter(|&i| grid[i] < grid[i - 1]).collect();
+    if let Some(&first) = decreasing.first() {
+        report.push(
+            ValidationIssueKind::NonMonotonicEnergy,
+            Some(temperature),
+            None,
+            Some(first),
+            format!(
+                "energy grid decreases at {} points, first from {} eV to {} eV",
+                decreasing.len(),
+                grid[first - 1],
+                grid[first]
+            ),
+        );
+    }
+    let repeated: Vec<usize> = (1..grid.len()).filter(|&i| grid[i] == grid[i - 1]).collect();
+    if let Some(&first) = repeated.first() {
+        report.push(
+            ValidationIssueKind::DuplicateEnergy,
+            Some(temperature),
+            None,
+            Some(first),
+            format!("energy grid repeats {} points, first {} eV", repeated.len(), grid[first]),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"name": "Li6", "temperatures": ["294"], "energy": {"294": [1.0, 2.0, 3.0, 4.0]},
+        "reactions": {"294": {
+            "1": {"cross_section": [3.0, 3.0, 3.5, 3.5], "threshold_idx": 0},
+            "2": {"cross_section": [1.0, 1.0, 1.0, 1.0], "threshold_idx": 0},
+            "102": {"cross_section": [2.0, 2.0, 2.0, 2.0], "threshold_idx": 0},
+            "51": {"cross_section": [0.5, 0.5], "threshold_idx": 2}}}}"#;
+
+    fn read(json: &str) -> Nuclide {
+        crate::nuclide::read_nuclide_from_json_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_valid_nuclide() {
+        let report = read(JSON).validate();
+        assert!(report.is_valid(), "{}", report);
+        assert_eq!(report.nuclide.as_deref(), Some("Li6"));
+        assert_eq!(report.to_string(), "Li6: no issues found");
+
+        for path in ["tests/Li6.json", "tests/Li7.json", "tests/Be9.json", "tests/Fe54.json"] {
+            let nuclide = crate::nuclide::read_nuclide_from_json(path, None).unwrap();
+            let report = nuclide.validate();
+            assert!(report.is_valid(), "{}", report);
+        }
+    }
+
+    #[test]
+    fn test_reports_each_kind_of_issue() {
+        let json = JSON
+            .replace("[1.0, 2.0, 3.0, 4.0]", "[1.0, 2.0, 2.0, 1.5]")
+            .replace("\"temperatures\": [\"294\"]", "\"temperatures\": [\"294\", \"600\"]")
+            .replace("[2.0, 2.0, 2.0, 2.0]", "[2.0, -0.5, 2.0, -1.0]")
+            .replace("[0.5, 0.5], \"threshold_idx\": 2", "[0.5], \"threshold_idx\": 2");
+        let mut nuclide = read(&json);
+        let reactions = nuclide.reactions.get_mut("294").unwrap();
+        reactions.get_mut(&2).unwrap().threshold_idx = 7;
+        let report = nuclide.validate();
+
+        let kinds: Vec<ValidationIssueKind> = report.issues.iter().map(|i| i.kind).collect();
+        for kind in [
+            ValidationIssueKind::NonMonotonicEnergy,
+            ValidationIssueKind::DuplicateEnergy,
+            ValidationIssueKind::NegativeCrossSection,
+            ValidationIssueKind::ThresholdBeyondGrid,
+            ValidationIssueKind::LengthMismatch,
+            ValidationIssueKind::TotalMismatch,
+            ValidationIssueKind::MissingTemperature,
+        ] {
+            assert!(kinds.contains(&kind), "{} not reported in\n{}", kind, report);
+        }
+        let negative = report.issues_of_kind(ValidationIssueKind::NegativeCrossSection);
+        assert_eq!(negative[0].mt, Some(102));
+        assert_eq!(negative[0].index, Some(1));
+        assert_eq!(report.issues_of_kind(ValidationIssueKind::NonMonotonicEnergy)[0].index, Some(3));
+        assert_eq!(
+            report.issues_of_kind(ValidationIssueKind::MissingTemperature)[0].temperature.as_deref(),
+            Some("600")
+        );
+
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"kind\": \"threshold_beyond_grid\""));
+        let parsed: ValidationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn test_reports_incomplete_total() {
+        let elastic_only = r#"{"name": "Li6", "temperatures": ["294"], "energy": {"294": [1.0, 2.0]},
+            "reactions": {"294": {"2": {"cross_section": [1.0, 1.0], "threshold_idx": 0}}}}"#;
+        let report = read(elastic_only).validate();
+        let incomplete = report.issues_of_kind(ValidationIssueKind::IncompleteTotal);
+        assert_eq!(report.issues.len(), 1, "{}", report);
+        assert_eq!(incomplete[0].mt, Some(1));
+        assert_eq!(
+            incomplete[0].message,
+            "is missing and cannot be summed from its partials, MT 3 not available"
+        );
+
+        let total_and_elastic = elastic_only.replace(
+            r#""2": {"#,
+            r#""1": {"cross_section": [3.0, 3.0], "threshold_idx": 0}, "2": {"#,
+        );
+        let report = read(&total_and_elastic).validate();
+        let incomplete = report.issues_of_kind(ValidationIssueKind::IncompleteTotal);
+        assert_eq!(report.issues.len(), 1, "{}", report);
+        assert!(incomplete[0].message.starts_with("cannot be checked"));
+    }
+
+    #[test]
+    fn test_total_mismatch_tolerance() {
+        let json = JSON.replace("[3.0, 3.0, 3.5, 3.5]", "[3.0, 3.0, 3.5, 3.5001]");
+        let nuclide = read(&json);
+        assert!(!nuclide.validate().is_valid());
+        assert!(nuclide.validate_with_tolerance(1e-3).is_valid());
+    }
+}
@@ -0,0 +1,91 @@
+// Python bindings for the validation module
+
+use crate::validation::ValidationReport;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+#[pyclass(name = "ValidationReport")]
+/// Problems found when validating a nuclide's data, see `Nuclide.validate`.
+///
+/// Attributes:
+///     nuclide (Optional[str]): Name of the validated nuclide.
+///     is_valid (bool): True when no issues were found.
+///     issues (List[Dict[str, Any]]): One dictionary per issue.
+pub struct PyValidationReport {
+    pub inner: ValidationReport,
+}
+
+#[pymethods]
+impl PyValidationReport {
+    /// Name of the validated nuclide, if known.
+    #[getter]
+    fn nuclide(&self) -> Option<String> {
+        self.inner.nuclide.clone()
+    }
+
+    /// True when no issues were found.
+    #[getter]
+    fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    /// Issues found, in a stable order.
+    ///
+    /// Returns:
+    ///     List[Dict[str, Any]]: Dictionaries with the keys:
+    ///
+    ///         - kind (str): "non_monotonic_energy", "duplicate_energy",
+    ///           "negative_cross_section", "threshold_beyond_grid",
+    ///           "length_mismatch", "total_mismatch", "incomplete_total" or
+    ///           "missing_temperature"
+    ///         - temperature (Optional[str]): Temperature the issue was found at
+    ///         - mt (Optional[int]): MT number of the reaction concerned
+    ///         - index (Optional[int]): First offending index
+    ///         - message (str): Description of the issue
+    #[getter]
+    fn issues(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.inner
+            .issues
+            .iter()
+            .map(|issue| {
+                let dict = PyDict::new(py);
+                dict.set_item("kind", issue.kind.as_str())?;
+                dict.set_item("temperature", &issue.temperature)?;
+                dict.set_item("mt", issue.mt)?;
+                dict.set_item("index", issue.index)?;
+                dict.set_item("message", &issue.message)?;
+                Ok(dict.into())
+            })
+            .collect()
+    }
+
+    /// Serialize the report to a JSON string.
+    ///
+    /// Returns:
+    ///     str: JSON with the nuclide name and the list of issues.
+    ///
+    /// Raises:
+    ///     ValueError: If serialization fails.
+    #[pyo3(text_signature = "(self)")]
+    fn to_json(&self) -> PyResult<String> {
+        self.inner
+            .to_json()
+            .map_err(pyo3::exceptions::PyValueError::new_err)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.issues.len()
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ValidationReport(nuclide={:?}, issues={})",
+            self.inner.nuclide.as_deref().unwrap_or("None"),
+            self.inner.issues.len()
+        )
+    }
+}
@@ -21,7 +21,7 @@ fn test_load_nuclear_data_from_json() {
     assert!(result.is_ok(), "Failed to load nuclide data from JSON");
     // Add nuclide after loading data
     material
-        .add_nuclide(nuclide_name, 1.0)
+        .add_nuclide(nuclide_name, 1.0, None)
         .expect("Failed to add nuclide");
     let nuclides = material.get_nuclides();
     assert!(
@@ -39,7 +39,7 @@ fn test_load_nuclear_data_from_json_file() {
     // Load the JSON content from the file at compile time
     let json_content = include_str!("../tests/Li6.json");
     material
-        .add_nuclide(nuclide_name, 1.0)
+        .add_nuclide(nuclide_name, 1.0, None)
         .expect("Failed to add nuclide");
     let result = material.load_nuclide_data(nuclide_name, json_content);
     assert!(result.is_ok(), "Failed to load nuclide data from JSON file");
@@ -58,7 +58,7 @@ fn test_reaction_mts_after_loading_json_file() {
     let nuclide_name = "Li6";
     let json_content = include_str!("../tests/Li6.json");
     material
-        .add_nuclide(nuclide_name, 1.0)
+        .add_nuclide(nuclide_name, 1.0, None)
         .expect("Failed to add nuclide");
     let result = material.load_nuclide_data(nuclide_name, json_content);
     assert!(result.is_ok(), "Failed to load nuclide data from JSON file");
@@ -95,4 +95,17 @@ def test_explicit_path_override_in_nuclide_loading():
 #     # This should raise a panic that gets converted to a Python exception
 #     with pytest.raises(Exception) as exc_info:
 #         m4mc.Config.set_cross_section("invalid-keyword")
-#     assert "Invalid cross section keyword" in str(exc_info.value)
\ No newline at end of file
+#     assert "Invalid cross section keyword" in str(exc_info.value)
+
+
+def test_set_cross_sections_normalises_nuclide_names():
+    original_configs = m4mc.Config.get_cross_sections()
+    try:
+        m4mc.Config.set_cross_sections({"Li-6": "../../tests/Li6.json", "3007.80c": "../../tests/Li7.json"})
+        assert m4mc.Config.get_cross_section("Li6") == "../../tests/Li6.json"
+        assert m4mc.Config.get_cross_section("li-7") == "../../tests/Li7.json"
+    finally:
+        # Restore original configuration
+        m4mc.Config.clear()
+        if original_configs:
+            m4mc.Config.set_cross_sections(original_configs)
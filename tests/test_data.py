@@ -13,3 +13,15 @@ def test_element_nuclides_li_and_be():
     nuclides = element_nuclides()
     assert sorted(nuclides['Li']) == ['Li6', 'Li7']
     assert sorted(nuclides['Be']) == ['Be9']
+
+def test_nuclide_name_conversions():
+    from materials_for_mc import to_gnds_name, to_zaid, to_hyphenated_name
+    for name in ['Li6', 'li6', 'Li-6', '3006', '3006.80c']:
+        assert to_gnds_name(name) == 'Li6'
+    assert to_gnds_name('U235m') == 'U235_m1'
+    assert to_gnds_name('6000') == 'C0'
+    assert to_zaid('Am242_m1') == 95242
+    assert to_zaid('Am242') == 95642
+    assert to_hyphenated_name('Am242_m1') == 'Am-242m1'
+    with pytest.raises(ValueError):
+        to_gnds_name('Xx6')
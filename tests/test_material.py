@@ -122,6 +122,31 @@ def test_add_element_not_found():
         mat.add_element('Xx', 1.0)
     assert 'not a recognized element symbol' in str(excinfo.value)
 
+def test_add_nuclide_weight_fraction():
+    mat = Material()
+    mat.add_nuclide('Li6', 0.5, 'wo')
+    mat.add_nuclide('Li7', 0.5, fraction_type='wo')
+    assert mat.fraction_types == [('Li6', 'wo'), ('Li7', 'wo')]
+    atom_fractions = mat.get_atom_fractions()
+    # Equal masses of each isotope means more of the lighter Li6 atoms
+    li6_mass = 6.0151228874
+    li7_mass = 7.0160034366
+    expected_li6 = (0.5 / li6_mass) / (0.5 / li6_mass + 0.5 / li7_mass)
+    assert abs(atom_fractions['Li6'] - expected_li6) < 1e-6
+    assert abs(sum(atom_fractions.values()) - 1.0) < 1e-12
+
+def test_add_element_weight_fraction():
+    mat = Material()
+    mat.add_element('Li', 1.0, 'wo')
+    atom_fractions = mat.get_atom_fractions()
+    assert abs(atom_fractions['Li6'] - 0.07589) < 1e-9
+    assert abs(atom_fractions['Li7'] - 0.92411) < 1e-9
+
+def test_invalid_fraction_type():
+    mat = Material()
+    with pytest.raises(ValueError, match="Invalid fraction type"):
+        mat.add_nuclide('Li6', 1.0, 'xo')
+
 def test_mean_free_path_lithium_14mev():
     from materials_for_mc import Config
     import math
@@ -496,4 +521,301 @@ def test_material_name_set_and_get():
     mat.name = "OtherName"
     assert mat.name == "OtherName"
     mat2 = Material(name="InitialName")
-    assert mat2.name == "InitialName"
\ No newline at end of file
+    assert mat2.name == "InitialName"
+def test_add_elements_from_formula():
+    mat = Material()
+    mat.add_elements_from_formula('Li4SiO4', 1.0)
+    nuclides = dict(mat.nuclides)
+    assert abs(nuclides['Li6'] - 4.0 / 9.0 * 0.07589) < 1e-9
+    assert abs(nuclides['O16'] + nuclides['O17'] + nuclides['O18'] - 4.0 / 9.0) < 1e-6
+
+def test_add_elements_from_formula_invalid():
+    mat = Material()
+    with pytest.raises(ValueError, match="not a recognized element symbol"):
+        mat.add_elements_from_formula('Qq2O', 1.0)
+
+def test_add_element_enriched():
+    mat = Material()
+    mat.add_element('Li', 1.0, enrichment=60.0, enrichment_target='Li6')
+    nuclides = dict(mat.nuclides)
+    assert abs(nuclides['Li6'] - 0.6) < 1e-12
+    assert abs(nuclides['Li7'] - 0.4) < 1e-12
+
+def test_add_element_enriched_weight_percent():
+    mat = Material()
+    mat.add_element('B', 1.0, enrichment=90.0, enrichment_target='B10', enrichment_type='wo')
+    nuclides = dict(mat.nuclides)
+    b10_mass = nuclides['B10'] * 10.0129369
+    b11_mass = nuclides['B11'] * 11.0093052
+    assert abs(b10_mass / (b10_mass + b11_mass) - 0.9) < 1e-6
+
+def test_add_element_enriched_requires_target():
+    mat = Material()
+    with pytest.raises(ValueError, match="must be specified together"):
+        mat.add_element('Li', 1.0, enrichment=60.0)
+    with pytest.raises(ValueError, match="not an isotope"):
+        mat.add_element('Li', 1.0, enrichment=60.0, enrichment_target='B10')
+
+def test_mix_by_volume():
+    tungsten = Material()
+    tungsten.add_element('W', 1.0)
+    tungsten.set_density('g/cm3', 19.3)
+    helium = Material()
+    helium.add_element('He', 1.0)
+    helium.set_density('g/cm3', 0.0001786)
+    mixture = Material.mix([(tungsten, 0.8), (helium, 0.2)], 'vo')
+    assert mixture.density_units == 'g/cm3'
+    assert abs(mixture.density - (0.8 * 19.3 + 0.2 * 0.0001786)) < 1e-9
+    w_atoms = tungsten.get_atoms_per_barn_cm()
+    mixed_atoms = mixture.get_atoms_per_barn_cm()
+    assert abs(mixed_atoms['W186'] - 0.8 * w_atoms['W186']) < 1e-12
+
+def test_mix_requires_density():
+    mat = Material()
+    mat.add_element('W', 1.0)
+    with pytest.raises(ValueError, match="no density"):
+        Material.mix([(mat, 1.0)], 'wo')
+
+def test_set_density_invalid_units():
+    mat = Material()
+    mat.add_element('Li', 1.0)
+    with pytest.raises(ValueError, match="Invalid density units"):
+        mat.set_density('g/m3', 1.0)
+
+def test_density_in_atom_units_reports_mass_density():
+    mat = Material()
+    mat.add_element('Li', 1.0)
+    mat.set_density('g/cc', 0.534)
+    total_atoms = sum(mat.get_atoms_per_barn_cm().values())
+    mat.set_density('atom/b-cm', total_atoms)
+    assert abs(mat.get_mass_density() - 0.534) < 1e-9
+    mat.set_density('atom/cm3', total_atoms * 1e24)
+    assert abs(mat.get_mass_density() - 0.534) < 1e-9
+
+def test_density_sum():
+    mat = Material()
+    mat.add_nuclide('Li6', 0.01)
+    mat.add_nuclide('Li7', 0.03)
+    mat.set_density('sum')
+    assert mat.density is None
+    assert mat.density_units == 'sum'
+    atoms = mat.get_atoms_per_barn_cm()
+    assert atoms['Li6'] == 0.01
+    assert atoms['Li7'] == 0.03
+    with pytest.raises(ValueError, match="value is required"):
+        mat.set_density('g/cm3')
+
+def test_from_library():
+    eurofer = Material.from_library('Eurofer97')
+    assert eurofer.name == 'Eurofer97'
+    assert eurofer.density == 7.798
+    assert eurofer.density_units == 'g/cm3'
+    assert 'Fe56' in dict(eurofer.nuclides)
+    with pytest.raises(ValueError, match="not found in the material library"):
+        Material.from_library('Unobtainium')
+
+def test_list_and_search_library():
+    from materials_for_mc import material_library_names, search_material_library
+    names = material_library_names()
+    for name in ['Eurofer97', 'Tungsten', 'Li4SiO4', 'PbLi', 'FLiBe', 'Water', 'B4C']:
+        assert name in names
+    concretes = search_material_library('concrete')
+    assert set(concretes) == {'ConcreteOrdinary', 'ConcreteBarite', 'ConcreteMagnetite'}
+
+def test_material_json_round_trip():
+    import json
+    mat = Material('breeder')
+    mat.add_nuclide('Li6', 0.6)
+    mat.add_nuclide('Li7', 0.4, 'wo')
+    mat.set_density('kg/m3', 534.0)
+    mat.temperature = '600'
+    mat.volume = 2.0
+    text = mat.to_json()
+    data = json.loads(text)
+    assert data['name'] == 'breeder'
+    assert data['composition'][1] == {'nuclide': 'Li7', 'fraction': 0.4, 'fraction_type': 'wo'}
+    restored = Material.from_json(text)
+    assert restored.name == 'breeder'
+    assert restored.nuclides == mat.nuclides
+    assert restored.fraction_types == mat.fraction_types
+    assert restored.density == 534.0
+    assert restored.density_units == 'kg/m3'
+    assert restored.temperature == '600'
+    assert restored.volume == 2.0
+    assert restored.to_json() == text
+
+def test_material_from_json_invalid():
+    with pytest.raises(ValueError):
+        Material.from_json('{"composition": [{"nuclide": "Li6", "fraction": -1.0}]}')
+
+def test_material_to_serpent():
+    mat = Material('light water')
+    mat.add_nuclide('H1', 2.0)
+    mat.add_nuclide('O16', 1.0)
+    mat.set_density('g/cm3', 1.0)
+    block = mat.to_serpent('03c')
+    assert 'mat light_water -1\n' in block
+    assert '1001.03c     2\n' in block
+
+def test_material_to_fispact():
+    mat = Material()
+    mat.add_element('Fe', 0.98, 'wo')
+    mat.add_element('C', 0.02, 'wo')
+    mat.set_density('g/cm3', 8.0)
+    with pytest.raises(ValueError):
+        mat.to_fispact_mass()
+    mat.volume = 500.0
+    lines = mat.to_fispact_mass().splitlines()
+    assert lines[0] == 'DENSITY 8'
+    assert lines[1] == 'MASS 4 2'
+    fuel = mat.to_fispact_fuel().splitlines()
+    assert fuel[1] == 'FUEL 6'
+    assert fuel[2].split()[0] == 'C12'
+
+def test_material_bulk_properties():
+    mat = Material()
+    mat.add_nuclide('H1', 2.0)
+    mat.add_nuclide('O16', 1.0)
+    mat.set_density('g/cm3', 1.0)
+    with pytest.raises(ValueError):
+        mat.get_total_mass()
+    mat.volume = 18.0
+    assert mat.get_total_mass() == pytest.approx(18.0)
+    atoms = mat.get_atoms_per_nuclide()
+    assert atoms['H1'] == pytest.approx(2 * atoms['O16'])
+    assert mat.get_total_atoms() == pytest.approx(sum(atoms.values()))
+    assert mat.get_average_molar_mass() == pytest.approx((2 * 1.00783 + 15.9949) / 3, abs=1e-4)
+    element_fractions = mat.get_element_atom_fractions()
+    assert element_fractions['H'] == pytest.approx(2.0 / 3.0)
+    mass_fractions = mat.get_element_mass_fractions()
+    assert mass_fractions['O'] == pytest.approx(0.888, abs=1e-3)
+    assert mat.get_electron_density() == pytest.approx(3.343e23, rel=1e-3)
+    assert mat.get_effective_z() == pytest.approx(7.42, abs=0.01)
+
+def test_material_element_tracking():
+    mat = Material()
+    mat.add_element('Fe', 0.9)
+    mat.add_nuclide('H1', 0.1)
+    assert mat.get_elements() == ['Fe', 'H']
+    assert mat.element_of('Fe56') == 'Fe'
+    assert mat.element_of('H1') is None
+    assert mat.get_direct_nuclides() == ['H1']
+    assert mat.get_expanded_nuclides() == ['Fe54', 'Fe56', 'Fe57', 'Fe58']
+    assert mat.get_element_fractions()['Fe'] == pytest.approx(0.9)
+    report = mat.composition_report()
+    assert 'element Fe' in report
+    assert 'nuclide' in report.splitlines()[-1]
+
+def test_material_remove_and_replace_element():
+    mat = Material()
+    mat.add_element('Pb', 0.99, 'wo')
+    mat.add_element('Li', 0.01, 'wo')
+    mat.replace_element('Pb', 'Bi')
+    assert mat.get_elements() == ['Bi', 'Li']
+    assert dict(mat.nuclides)['Bi209'] == pytest.approx(0.99)
+    mat.remove_element('Li')
+    assert mat.get_elements() == ['Bi']
+    with pytest.raises(ValueError):
+        mat.remove_element('Li')
+
+def test_material_impurities():
+    mat = Material()
+    mat.add_element('Fe', 0.98, 'wo')
+    mat.add_element('C', 0.02, 'wo')
+    mat.add_impurity('Co', 50, 'wppm')
+    mat.add_impurity('Ag', 2, 'appm')
+    assert mat.impurities == [('Co', 50.0, 'wppm'), ('Ag', 2.0, 'appm')]
+    assert mat.get_element_mass_fractions()['Co'] == pytest.approx(50e-6)
+    assert mat.get_element_atom_fractions()['Ag'] == pytest.approx(2e-6)
+    assert 'impurity Co (50 wppm)' in mat.composition_report()
+    with pytest.raises(ValueError):
+        mat.add_impurity('Fe', 10)
+    with pytest.raises(ValueError):
+        mat.add_impurity('Nb', 10, 'ppm')
+    mat.remove_impurity('Ag')
+    assert mat.impurities == [('Co', 50.0, 'wppm')]
+
+def test_material_remove_replace_and_scale_nuclides():
+    mat = Material()
+    mat.add_nuclide('Li6', 0.6)
+    mat.add_nuclide('Li7', 0.4)
+    mat.add_nuclide('Be9', 1.0)
+    mat.remove_nuclide('Be9')
+    assert mat.get_nuclide_names() == ['Li6', 'Li7']
+    with pytest.raises(ValueError):
+        mat.remove_nuclide('Be9')
+    mat.replace_nuclide('Li6', 'H3')
+    assert dict(mat.nuclides) == {'H3': 0.6, 'Li7': 0.4}
+    mat.scale(2.0)
+    assert dict(mat.nuclides)['H3'] == pytest.approx(1.2)
+    mat.scale(0.5, nuclide='Li7')
+    assert dict(mat.nuclides)['Li7'] == pytest.approx(0.4)
+    mat.normalize()
+    assert sum(dict(mat.nuclides).values()) == pytest.approx(1.0)
+    with pytest.raises(ValueError):
+        mat.scale(-1.0)
+
+def test_material_set_atom_densities():
+    mat = Material()
+    mat.add_element('Li', 1.0)
+    mat.set_density('g/cm3', 0.534)
+    mat.set_atom_densities({'Li6': 0.01, 'Li7': 0.03, 'H3': 1e-6})
+    assert mat.density_units == 'atom/b-cm'
+    assert mat.density == pytest.approx(0.040001)
+    assert mat.get_atoms_per_barn_cm()['H3'] == pytest.approx(1e-6)
+    with pytest.raises(ValueError):
+        mat.set_atom_densities({'Li6': -1.0})
+    with pytest.raises(ValueError):
+        mat.set_atom_densities({'Li6': 1.0}, 'g/cm3')
+
+def test_material_add_nuclide_any_name_form():
+    mat = Material()
+    mat.add_nuclide('Li-6', 0.5)
+    mat.add_nuclide('3007.80c', 0.5)
+    mat.add_nuclide('li6', 0.6)
+    assert mat.get_nuclide_names() == ['Li6', 'Li7']
+    assert dict(mat.nuclides)['Li6'] == 0.6
+
+
+def test_temperature_between_data_temperatures():
+    def elastic(temperature, method):
+        mat = Material()
+        mat.add_nuclide('Be9', 1.0)
+        mat.set_density('g/cm3', 1.85)
+        mat.temperature = temperature
+        mat.temperature_method = method
+        mat.read_nuclides_from_json({'Be9': 'tests/Be9.json'})
+        xs, energy = mat.macroscopic_cross_section(2)
+        return dict(zip(energy, xs))
+
+    low = elastic(294.0, 'nearest')
+    high = elastic(300.0, 'nearest')
+    assert elastic(299.0, 'nearest') == high
+    interpolated = elastic(297.0, 'interpolation')
+    common = [e for e in interpolated if e in low and e in high]
+    for e in common[::100]:
+        assert interpolated[e] == pytest.approx(0.5 * (low[e] + high[e]))
+    assert elastic(297.0, 'stochastic') in (low, high)
+
+
+def test_temperature_accepts_numbers_and_methods():
+    mat = Material()
+    mat.temperature = 600.0
+    assert mat.temperature == '600'
+    assert mat.temperature_method == 'nearest'
+    mat.temperature_method = 'Interpolation'
+    assert mat.temperature_method == 'interpolation'
+    with pytest.raises(ValueError):
+        mat.temperature_method = 'cubic'
+
+
+def test_temperature_method_broadening():
+    mat = Material()
+    mat.add_nuclide('Be9', 1.0)
+    mat.set_density('g/cm3', 1.85)
+    mat.temperature = 1200.0
+    mat.temperature_method = 'broadening'
+    mat.read_nuclides_from_json({'Be9': 'tests/Be9.json'})
+    xs, energy = mat.macroscopic_cross_section(2)
+    assert len(xs) == len(energy) > 0
+    assert all(value >= 0 for value in xs)
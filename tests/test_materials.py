@@ -82,3 +82,121 @@ def test_materials_be9_selective_temperature_union():
     mats = Materials([m1, m2])
     # Explicitly trigger eager union load across temperatures 294 & 300
     mats.read_nuclides_from_json({"Be9": "tests/Be9.json"})
+
+def test_materials_json_round_trip():
+    mat1 = Material('steel')
+    mat1.add_element('Fe', 1.0)
+    mat1.set_density('g/cm3', 7.8)
+    mat2 = Material('water')
+    mat2.add_elements_from_formula('H2O', 1.0)
+    mats = Materials([mat1, mat2])
+    text = mats.to_json()
+    restored = Materials.from_json(text)
+    assert len(restored) == 2
+    assert restored[0].name == 'steel'
+    assert restored[0].density == 7.8
+    assert restored[1].nuclides == mat2.nuclides
+    assert restored.to_json() == text
+
+def test_materials_export_to_openmc_xml(tmp_path):
+    mat1 = Material('lithium')
+    mat1.add_element('Li', 1.0)
+    mat1.set_density('g/cm3', 0.534)
+    mat2 = Material('iron')
+    mat2.add_element('Fe', 1.0, 'wo')
+    mat2.set_density('g/cm3', 7.8)
+    mat2.volume = 3.0
+    mats = Materials([mat1, mat2])
+    path = tmp_path / 'materials.xml'
+    mats.export_to_openmc_xml(str(path))
+    text = path.read_text()
+    assert text == mats.to_openmc_xml()
+    assert '<material id="1" name="lithium" temperature="294">' in text
+    assert '<material id="2" name="iron" temperature="294" volume="3">' in text
+    assert '<density units="g/cm3" value="7.8" />' in text
+    assert 'wo="' in text
+
+def test_materials_read_openmc_xml(tmp_path):
+    mat1 = Material('breeder')
+    mat1.add_element('Li', 1.0, enrichment=60.0, enrichment_target='Li6')
+    mat1.set_density('g/cm3', 0.5)
+    mat1.temperature = '600'
+    mats = Materials([mat1])
+    path = tmp_path / 'materials.xml'
+    mats.export_to_openmc_xml(str(path))
+    restored = Materials.read_openmc_xml(str(path))
+    assert len(restored) == 1
+    assert restored[0].name == 'breeder'
+    assert restored[0].temperature == '600'
+    assert restored[0].nuclides == mat1.nuclides
+    assert restored.to_openmc_xml() == mats.to_openmc_xml()
+
+def test_materials_from_openmc_xml_elements():
+    xml = '''<materials>
+      <material id="1" name="fuel">
+        <density units="g/cm3" value="10.4" />
+        <element name="U" ao="1.0" enrichment="4.5" />
+        <element name="O" ao="2.0" />
+      </material>
+    </materials>'''
+    mats = Materials.from_openmc_xml(xml)
+    assert mats[0].name == 'fuel'
+    assert 'U235' in dict(mats[0].nuclides)
+    assert 'O16' in dict(mats[0].nuclides)
+    with pytest.raises(ValueError):
+        Materials.from_openmc_xml('<materials><material>')
+
+def test_materials_mcnp_round_trip(tmp_path):
+    mat1 = Material('steel')
+    mat1.add_element('Fe', 0.98, 'wo')
+    mat1.add_element('C', 0.02, 'wo')
+    mat1.set_density('g/cm3', 7.8)
+    mat2 = Material('water')
+    mat2.add_nuclide('H1', 2.0)
+    mat2.add_nuclide('O16', 1.0)
+    mat2.set_density('atom/b-cm', 0.1)
+    mats = Materials([mat1, mat2])
+    text = mats.to_mcnp('80c')
+    assert 'm1    6012.80c' in text
+    assert 'm2    1001.80c' in text
+    path = tmp_path / 'materials.mcnp'
+    mats.export_to_mcnp(str(path), '80c')
+    restored = Materials.read_mcnp(str(path))
+    assert len(restored) == 2
+    assert restored[0].name == 'steel'
+    assert restored[0].nuclides == mat1.nuclides
+    assert restored[0].density == 7.8
+    assert restored[1].density_units == 'atom/b-cm'
+    assert mat1.get_mcnp_cell_density() == -7.8
+    assert mat2.get_mcnp_cell_density() == 0.1
+
+def test_materials_from_mcnp_deck():
+    deck = """title
+1 1 -2.0 -1 imp:n=1
+2 0 1 imp:n=0
+
+1 so 5
+
+m1 6000.80c 1.0
+"""
+    mats = Materials.from_mcnp(deck)
+    assert len(mats) == 1
+    assert mats[0].density == 2.0
+    assert set(dict(mats[0].nuclides)) == {'C0'}
+    with pytest.raises(ValueError):
+        Materials.from_mcnp('m1 1001 1.0 8016 -1.0')
+
+def test_materials_to_serpent(tmp_path):
+    mat1 = Material('first')
+    mat1.add_nuclide('Be9', 1.0)
+    mat1.set_density('g/cm3', 1.85)
+    mat2 = Material()
+    mat2.add_nuclide('Be9', 1.0)
+    mat2.set_density('g/cm3', 1.85)
+    mats = Materials([mat1, mat2])
+    path = tmp_path / 'materials.serpent'
+    mats.export_to_serpent(str(path), '03c')
+    text = path.read_text()
+    assert text == mats.to_serpent('03c')
+    assert 'mat first -1.85\n' in text
+    assert 'mat m2 -1.85\n' in text
@@ -893,3 +893,104 @@ def test_nuclide_path_normalization():
     xs_abs, _ = li6_abs.microscopic_cross_section("(n,gamma)")
     
     assert xs_rel == xs_abs, "Relative and absolute paths to same file should give identical results"
+
+
+def test_atomic_weight_ratio_and_metastable():
+    li6 = Nuclide("Li6")
+    assert li6.atomic_weight_ratio is None
+    li6.read_nuclide_from_json("tests/Li6.json")
+    assert li6.atomic_weight_ratio == pytest.approx(5.9634)
+    assert li6.metastable == 0
+    assert li6.atomic_mass == pytest.approx(6.0151, abs=1e-3)
+
+
+def test_microscopic_cross_section_between_temperatures():
+    nuc = Nuclide('Be9')
+    nuc.read_nuclide_from_json('tests/Be9.json')
+    xs_300, energy_300 = nuc.microscopic_cross_section(2, temperature='300')
+    assert nuc.microscopic_cross_section(2, temperature=299.0) == (xs_300, energy_300)
+    assert nuc.microscopic_cross_section(2, temperature='299', method='nearest') == (xs_300, energy_300)
+    xs, energy = nuc.microscopic_cross_section(2, temperature=297.0, method='interpolation')
+    assert len(xs) == len(energy)
+    with pytest.raises(ValueError):
+        nuc.microscopic_cross_section(2, temperature=297.0, method='cubic')
+
+
+def test_doppler_broaden_to_new_temperature():
+    nuc = Nuclide('Be9')
+    nuc.read_nuclide_from_json('tests/Be9.json', temperatures=['294'])
+    key = nuc.doppler_broaden(600.0)
+    assert key == '600'
+    assert '600' in nuc.loaded_temperatures
+    xs, energy = nuc.microscopic_cross_section(2, temperature='600')
+    assert len(xs) == len(energy)
+    assert nuc.doppler_broaden(294.0) == '294'
+    with pytest.raises(ValueError):
+        nuc.doppler_broaden(100.0)
+
+
+def test_reactions_include_interpolation_regions():
+    nuc = Nuclide('Li6')
+    nuc.read_nuclide_from_json('tests/Li6.json')
+    reaction = nuc.reactions['294'][102]
+    assert reaction['interpolation'] == [2]
+    assert reaction['breakpoints'] == []
+
+
+def test_reconstruct_redundant_reactions():
+    nuc = Nuclide('Li6')
+    nuc.read_nuclide_from_json('tests/Li6.json')
+    assert nuc.reactions['294'][1]['derived'] is False
+    assert nuc.reconstruct_redundant_reactions() == []
+
+
+def test_validate_reports_issues():
+    nuc = Nuclide('Li6')
+    nuc.read_nuclide_from_json('tests/Li6.json')
+    report = nuc.validate()
+    assert report.is_valid
+    assert len(report) == 0
+    assert report.nuclide == 'Li6'
+    assert report.issues == []
+    assert '"issues": []' in report.to_json()
+    assert nuc.validate(tolerance=1e-3).is_valid
+
+
+def test_binary_round_trip(tmp_path):
+    from materials_for_mc import convert_json_to_binary, read_nuclide_from_binary
+    binary_path = str(tmp_path / 'Be9.bin')
+    convert_json_to_binary('tests/Be9.json', binary_path)
+    json_nuc = Nuclide('Be9')
+    json_nuc.read_nuclide_from_json('tests/Be9.json')
+
+    nuc = Nuclide('Be9')
+    nuc.read_nuclide_from_json(binary_path)
+    assert nuc.available_temperatures == json_nuc.available_temperatures
+    assert nuc.reactions == json_nuc.reactions
+    assert nuc.energy == json_nuc.energy
+
+    selected = read_nuclide_from_binary(binary_path, temperatures=['300'], mts=[2, 102])
+    assert selected.loaded_temperatures == ['300']
+    assert selected.available_temperatures == ['294', '300']
+    assert selected.reaction_mts == [2, 102]
+
+    copy_path = str(tmp_path / 'Be9_copy.bin')
+    selected.export_to_binary(copy_path)
+    assert read_nuclide_from_binary(copy_path).reaction_mts == [2, 102]
+
+
+def test_read_ace_file():
+    from materials_for_mc import read_nuclide_from_ace
+    nuc = read_nuclide_from_ace('tests/Li6.ace')
+    assert nuc.name == 'Li6'
+    assert nuc.available_temperatures == ['294']
+    assert nuc.atomic_weight_ratio == pytest.approx(5.9634)
+    assert 2 in nuc.reaction_mts and 105 in nuc.reaction_mts
+
+    json_nuc = Nuclide('Li6')
+    json_nuc.read_nuclide_from_json('tests/Li6.json')
+    from_path = Nuclide('Li6')
+    from_path.read_nuclide_from_json('tests/Li6.ace')
+    xs, _ = from_path.microscopic_cross_section(2, temperature='294')
+    json_xs, _ = json_nuc.microscopic_cross_section(2, temperature='294')
+    assert xs == pytest.approx(json_xs, rel=1e-10)